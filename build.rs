@@ -1,124 +1,145 @@
 use std::{
     env::var,
-    fs::{read_dir, read_to_string, File},
+    fs::{create_dir_all, read_dir, read_to_string, File},
     io::Write,
+    iter::once,
 };
 
+use ad::{Ad, Size};
+use article::{Article, Fragment, Image, Poll};
+use chrono::{DateTime, NaiveTime, TimeZone, Utc};
+use classified::Classified;
+use crossword::Crossword;
+use event::{calendar, Event};
 use itertools::Itertools;
-use proc_macro2::TokenTree;
-use syn::{parse_file, Item, ItemFn, Signature, Stmt, StmtMacro};
+use nom::combinator::all_consuming;
+use routes::{HOROSCOPES, HOROSCOPES_DATE, ROUTES};
+use serde::Serialize;
+use strum::VariantArray;
+
+#[allow(dead_code)]
+#[path = "src/ad.rs"]
+mod ad;
+#[allow(dead_code)]
+#[path = "src/article.rs"]
+mod article;
+#[allow(dead_code)]
+#[path = "src/classified.rs"]
+mod classified;
+#[allow(dead_code)]
+#[path = "src/crossword.rs"]
+mod crossword;
+#[allow(dead_code)]
+#[path = "src/event.rs"]
+mod event;
+#[allow(dead_code)]
+#[path = "src/routes.rs"]
+mod routes;
+
+const SITE: &str = "https://thewaratah.pages.dev";
+const TITLE: &str = "The Waratah";
+const API_VERSION: u32 = 1;
+const COMMENTS_API: &str = "http://127.0.0.1:3001";
+const ANALYTICS_API: &str = "http://127.0.0.1:3002";
+const DESCRIPTION: &str =
+    "Australia's most serious newspaper, proudly brought to you by incredible (and a few credible) reporters.";
+
+fn api(name: &str, local: &str) -> String {
+    var(name).unwrap_or_else(|_| {
+        if var("PROFILE").as_deref() == Ok("release") {
+            String::new()
+        } else {
+            local.to_string()
+        }
+    })
+}
 
 fn main() {
+    println!("cargo:rustc-env=SITE={SITE}");
+    println!(
+        "cargo:rustc-env=COMMENTS_API={}",
+        api("COMMENTS_API", COMMENTS_API)
+    );
+    println!("cargo:rerun-if-env-changed=COMMENTS_API");
+    println!(
+        "cargo:rustc-env=ANALYTICS_API={}",
+        api("ANALYTICS_API", ANALYTICS_API)
+    );
+    println!("cargo:rerun-if-env-changed=ANALYTICS_API");
+    println!("cargo:rerun-if-changed=src/ad.rs");
+    println!("cargo:rerun-if-changed=src/article.rs");
+    println!("cargo:rerun-if-changed=src/crossword.rs");
+    println!("cargo:rerun-if-changed=src/event.rs");
+    println!("cargo:rerun-if-changed=src/routes.rs");
+    println!("cargo:rerun-if-changed=src/classified.rs");
+    println!("cargo:rerun-if-changed=src/ads");
     println!("cargo:rerun-if-changed=src/articles");
+    println!("cargo:rerun-if-changed=src/classifieds");
     println!("cargo:rerun-if-changed=src/crosswords");
-    println!("cargo:rerun-if-changed=src/images/ads");
-    let article_ids = collect_articles();
-    collect_ads();
+    println!("cargo:rerun-if-changed=src/events");
+    println!("cargo:rerun-if-changed=src/redirects");
+    println!("cargo:rerun-if-changed=src/images/horizontal-ads");
+    println!("cargo:rerun-if-changed=src/images/vertical-ads");
+    let articles = collect_articles();
+    let landing_pages = collect_landing_pages();
+    collect_ads(&landing_pages);
     let crosswords = collect_crosswords();
-    generate_sitemap(&article_ids, crosswords);
+    collect_classifieds();
+    let events = collect_events(&articles);
+    create_dir_all(public("")).unwrap();
+    collect_redirects(&articles);
+    generate_sitemap(&articles, crosswords.len(), &events, &landing_pages);
+    generate_calendars(&events);
+    generate_feeds(&articles);
+    generate_api(&articles, &crosswords);
 
     dbg!(var("OUT_DIR").unwrap());
 }
 
-fn generate_sitemap(article_ids: &[String], crosswords: usize) {
-    let sitemap = parse_file(include_str!("src/components.rs"))
-        .unwrap()
-        .items
+fn generate_sitemap(
+    articles: &[Article],
+    crosswords: usize,
+    events: &[Event],
+    landing_pages: &[Article],
+) {
+    let urls = ROUTES
         .iter()
-        .filter_map(|item| match item {
-            Item::Fn(ItemFn {
-                sig: Signature { ident, .. },
-                block,
-                ..
-            }) if (*ident == "App") => Some(
-                block
-                    .stmts
-                    .iter()
-                    .filter_map(|statement| match statement {
-                        Stmt::Macro(StmtMacro { mac, .. }) => {
-                            let tokens = mac.tokens.clone().into_iter().collect_vec();
-                            Some(
-                                tokens
-                                    .split(|token| {
-                                        matches!(
-                                            token,
-                                            TokenTree::Punct(punct)
-                                                if punct.as_char() == '='
-                                        )
-                                    })
-                                    .filter_map(|sub| (!sub.is_empty()).then_some(sub.to_vec()))
-                                    .tuple_windows()
-                                    .filter_map(|(a, b)| {
-                                        let [second_last, last] = a.last_chunk().unwrap();
-                                        matches!(
-                                            (second_last, last),
-                                            (
-                                                TokenTree::Ident(second_last),
-                                                TokenTree::Ident(last)
-                                            )
-                                            if *second_last == "Route" && *last == "path"
-                                        )
-                                        .then(|| b.first().unwrap().clone())
-                                        .and_then(
-                                            |token| {
-                                                let TokenTree::Literal(literal) = token else {
-                                                    return None;
-                                                };
-                                                Some(
-                                                    literal
-                                                        .to_string()
-                                                        .strip_prefix('"')
-                                                        .unwrap()
-                                                        .strip_suffix('"')
-                                                        .unwrap()
-                                                        .to_string(),
-                                                )
-                                            },
-                                        )
-                                    })
-                                    .collect_vec(),
-                            )
-                        }
-                        _ => None,
-                    })
-                    .exactly_one()
-                    .unwrap(),
-            ),
-            _ => None,
-        })
-        .exactly_one()
-        .unwrap()
-        .iter()
-        .flat_map(|route| match route.as_str() {
-            "/articles/:id" => article_ids
-                .iter()
-                .map(|id| format!("/articles/{id}"))
-                .collect_vec(),
-            "/crosswords/:id" => (0..crosswords)
-                .map(|id| format!("/crosswords/{id}"))
-                .collect_vec(),
-            "/*" => Vec::new(),
-            _ => {
-                vec![route.clone()]
-            }
+        .filter(|route| route.listed)
+        .flat_map(|route| {
+            route
+                .expand(articles, crosswords, events, landing_pages)
+                .into_iter()
+                .map(move |page| {
+                    format!(
+                        "<url><loc>{SITE}{}</loc>{}<changefreq>{}</changefreq><priority>{:.1}</priority></url>",
+                        escape(&page.path),
+                        page.last_modified
+                            .map(|date| format!("<lastmod>{date}</lastmod>"))
+                            .unwrap_or_default(),
+                        route.change_frequency,
+                        route.priority,
+                    )
+                })
         })
-        .collect_vec()
-        .join("\n");
-    File::create(var("OUT_DIR").unwrap() + "/sitemap.txt")
+        .join("");
+    File::create(public("sitemap.xml"))
         .unwrap()
-        .write_all(sitemap.as_bytes())
+        .write_all(
+            format!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+                 <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">{urls}</urlset>"
+            )
+            .as_bytes(),
+        )
         .unwrap();
 }
 
-fn collect_crosswords() -> usize {
+fn collect_crosswords() -> Vec<Crossword> {
     let crosswords = read_dir("src/crosswords")
         .unwrap()
-        .map(|entry| {
-            read_to_string(entry.unwrap().path())
-                .unwrap()
-                .trim()
-                .to_string()
-        })
+        .map(|entry| entry.unwrap().path())
+        .sorted_unstable()
+        .map(|path| read_to_string(path).unwrap().trim().to_string())
         .collect_vec();
     File::create(var("OUT_DIR").unwrap() + "/crosswords")
         .unwrap()
@@ -130,12 +151,103 @@ fn collect_crosswords() -> usize {
                 .collect_vec(),
         )
         .unwrap();
-    crosswords.len()
+    crosswords
+        .into_iter()
+        .map(|crossword| Crossword::parse(crossword.leak()))
+        .collect()
+}
+
+fn collect_events(articles: &[Article]) -> Vec<Event> {
+    let (data, events): (Vec<_>, Vec<_>) = read_dir("src/events")
+        .unwrap()
+        .map(|entry| {
+            let entry = entry.unwrap();
+            let event = read_to_string(entry.path()).unwrap();
+            let id = entry.file_name();
+            let id = id.to_string_lossy();
+            let data = format!("{} {}", id, event.trim());
+            let (_, parsed) = all_consuming(Event::parse)(data.clone().leak())
+                .unwrap_or_else(|error| panic!("invalid event {id}: {error}"));
+            assert!(
+                parsed.end >= parsed.start,
+                "event {id} ends before it starts"
+            );
+            (format!("{} {}", data.len(), data), parsed)
+        })
+        .unzip();
+    File::create(var("OUT_DIR").unwrap() + "/events")
+        .unwrap()
+        .write_all(data.join("\n").as_bytes())
+        .unwrap();
+    for article in articles {
+        for fragment in &article.fragments {
+            if let Fragment::Event(id) = fragment {
+                assert!(
+                    events.iter().any(|event| event.id == *id),
+                    "article {} links to unknown event {id}",
+                    article.id
+                );
+            }
+        }
+    }
+    events
+        .into_iter()
+        .sorted_unstable_by_key(|event| event.start)
+        .collect()
 }
 
-fn collect_articles() -> Vec<String> {
+fn generate_calendars(events: &[Event]) {
+    create_dir_all(public("events")).unwrap();
+    File::create(public("events.ics"))
+        .unwrap()
+        .write_all(calendar(&format!("{TITLE} Events"), SITE, events).as_bytes())
+        .unwrap();
+    for event in events {
+        File::create(public(&format!("events/{}.ics", event.id)))
+            .unwrap()
+            .write_all(calendar(TITLE, SITE, [event]).as_bytes())
+            .unwrap();
+    }
+}
+
+fn collect_classifieds() {
+    let data = read_dir("src/classifieds")
+        .unwrap()
+        .flat_map(|category_entry| {
+            let category_entry = &category_entry.unwrap();
+            read_dir(category_entry.path())
+                .unwrap()
+                .map(|classified_entry| {
+                    let entry = classified_entry.unwrap();
+                    let classified = read_to_string(entry.path()).unwrap();
+                    let classified = classified.trim();
+                    let category = category_entry.file_name();
+                    let category = category.to_string_lossy();
+                    let id = entry.file_name();
+                    let id = id.to_string_lossy();
+                    let data = format!("{} {} {} {}", category.len(), category, id, classified);
+                    let (_, parsed) = all_consuming(Classified::parse)(data.clone().leak())
+                        .unwrap_or_else(|error| {
+                            panic!("invalid classified {category}/{id}: {error}")
+                        });
+                    assert!(
+                        parsed.expires >= parsed.posted,
+                        "classified {category}/{id} expires before it was posted"
+                    );
+                    format!("{} {}", data.len(), data)
+                })
+                .collect_vec()
+        })
+        .join("\n");
+    File::create(var("OUT_DIR").unwrap() + "/classifieds")
+        .unwrap()
+        .write_all(data.as_bytes())
+        .unwrap();
+}
+
+fn collect_articles() -> Vec<Article> {
     let topics = read_dir("src/articles").unwrap();
-    let (articles, id): (Vec<_>, Vec<_>) = topics
+    let (data, articles): (Vec<_>, Vec<_>) = topics
         .flat_map(|topic_entry| {
             let topic_entry = &topic_entry.unwrap();
             read_dir(topic_entry.path())
@@ -146,28 +258,385 @@ fn collect_articles() -> Vec<String> {
                     let article = article.trim();
                     let topic = topic_entry.file_name();
                     let topic = topic.to_string_lossy();
-                    let id = entry.file_name();
-                    let id = id.to_string_lossy();
+                    let id = entry.file_name().to_string_lossy().to_lowercase();
                     let data = format!("{} {} {} {}", topic.len(), topic, id, article);
-                    (format!("{} {}", data.len(), data), id.to_string())
+                    let (_, parsed) = all_consuming(Article::parse)(data.clone().leak())
+                        .unwrap_or_else(|error| panic!("invalid article {topic}/{id}: {error}"));
+                    (format!("{} {}", data.len(), data), parsed)
                 })
                 .collect_vec()
         })
         .unzip();
     File::create(var("OUT_DIR").unwrap() + "/articles")
         .unwrap()
-        .write_all(articles.into_iter().collect_vec().join("\n").as_bytes())
+        .write_all(data.into_iter().collect_vec().join("\n").as_bytes())
         .unwrap();
-    id
+    if let Some(id) = articles
+        .iter()
+        .flat_map(|article| &article.fragments)
+        .filter_map(|fragment| match fragment {
+            Fragment::Poll(poll) => Some(poll.id),
+            _ => None,
+        })
+        .duplicates()
+        .next()
+    {
+        panic!("poll id {id} is used more than once");
+    }
+    articles
+        .into_iter()
+        .sorted_unstable_by_key(|article| -(article.index as i128))
+        .collect()
 }
 
-fn collect_ads() {
-    let ads: Vec<_> = read_dir("src/images/horizontal-ads")
+fn collect_redirects(articles: &[Article]) {
+    let directories = read_dir("src/articles")
         .unwrap()
+        .flat_map(|topic| read_dir(topic.unwrap().path()).unwrap())
         .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
-        .collect();
+        .collect_vec();
+    if let Some(id) = directories
+        .iter()
+        .map(|directory| directory.to_lowercase())
+        .duplicates()
+        .next()
+    {
+        panic!("more than one article has the id {id} when case is ignored");
+    }
+    let redirects = read_to_string("src/redirects").unwrap();
+    let redirects = redirects
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            line.split_whitespace()
+                .collect_tuple::<(_, _)>()
+                .unwrap_or_else(|| panic!("invalid redirect {line:?}"))
+        })
+        .collect_vec();
+    for (alias, target) in &redirects {
+        assert!(
+            articles
+                .iter()
+                .any(|article| article.id.eq_ignore_ascii_case(target)),
+            "redirect {alias} points to missing article {target}"
+        );
+        assert!(
+            !articles
+                .iter()
+                .any(|article| article.id.eq_ignore_ascii_case(alias)),
+            "redirect {alias} shadows an existing article"
+        );
+    }
+    for ((a, _), (b, _)) in redirects.iter().tuple_combinations() {
+        assert!(!a.eq_ignore_ascii_case(b), "redirect {a} is listed twice");
+    }
+    File::create(var("OUT_DIR").unwrap() + "/redirects")
+        .unwrap()
+        .write_all(
+            redirects
+                .iter()
+                .map(|(alias, target)| format!("{alias} {}", target.to_lowercase()))
+                .join("\n")
+                .as_bytes(),
+        )
+        .unwrap();
+    File::create(public("_redirects"))
+        .unwrap()
+        .write_all(
+            directories
+                .iter()
+                .filter(|directory| **directory != directory.to_lowercase())
+                .map(|directory| (directory.as_str(), directory.as_str()))
+                .chain(redirects.iter().copied())
+                .map(|(alias, target)| {
+                    format!("/articles/{alias} /articles/{} 301", target.to_lowercase())
+                })
+                // Dated horoscopes aren't prerendered, so serve the horoscopes page and let it
+                // hydrate the date instead of falling through to 404.html.
+                .chain([format!(
+                    "{} {} 200",
+                    HOROSCOPES_DATE.path.replace(":date", "*"),
+                    HOROSCOPES.path
+                )])
+                .join("\n")
+                .as_bytes(),
+        )
+        .unwrap();
+}
+
+fn collect_landing_pages() -> Vec<Article> {
+    const TOPIC: &str = "Sponsored";
+    let (data, pages): (Vec<_>, Vec<_>) = read_dir("src/ads")
+        .unwrap()
+        .map(|entry| {
+            let entry = entry.unwrap();
+            let page = read_to_string(entry.path()).unwrap();
+            let slug = entry.file_name();
+            let slug = slug.to_string_lossy();
+            let data = format!("{} {} {} {}", TOPIC.len(), TOPIC, slug, page.trim());
+            let (_, parsed) = all_consuming(Article::parse)(data.clone().leak())
+                .unwrap_or_else(|error| panic!("invalid ad landing page {slug}: {error}"));
+            assert!(
+                parsed
+                    .fragments
+                    .iter()
+                    .all(|fragment| matches!(fragment, Fragment::Text(_) | Fragment::Image(_))),
+                "ad landing page {slug} can only hold text and images"
+            );
+            (format!("{} {}", data.len(), data), parsed)
+        })
+        .unzip();
+    File::create(var("OUT_DIR").unwrap() + "/landing-pages")
+        .unwrap()
+        .write_all(data.join("\n").as_bytes())
+        .unwrap();
+    pages
+}
+
+fn collect_ads(landing_pages: &[Article]) {
+    let data = Size::VARIANTS
+        .iter()
+        .flat_map(|size| collect_ad_size(*size, landing_pages))
+        .join("\n");
     File::create(var("OUT_DIR").unwrap() + "/ads")
         .unwrap()
-        .write_all(ads.join("\n").as_bytes())
+        .write_all(data.as_bytes())
         .unwrap();
 }
+
+fn collect_ad_size(size: Size, landing_pages: &[Article]) -> Vec<String> {
+    let (manifests, images): (Vec<_>, Vec<_>) =
+        read_dir(format!("src/images/{}", size.directory()))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .partition(|path| path.extension().is_some_and(|extension| extension == "ad"));
+    for image in &images {
+        assert!(
+            manifests.contains(&image.with_extension("ad")),
+            "ad {} has no manifest",
+            image.display()
+        );
+    }
+    manifests
+        .iter()
+        .map(|manifest| {
+            let name = manifest.file_stem().unwrap().to_string_lossy();
+            let image = images
+                .iter()
+                .find(|image| image.file_stem() == manifest.file_stem())
+                .unwrap_or_else(|| panic!("ad manifest {name} has no image"));
+            let image = image.file_name().unwrap().to_string_lossy();
+            let data = format!(
+                "{size} {image} {}",
+                read_to_string(manifest).unwrap().trim()
+            );
+            let (_, parsed) = all_consuming(Ad::parse)(data.clone().leak())
+                .unwrap_or_else(|error| panic!("invalid ad manifest {name}: {error}"));
+            assert!(parsed.weight > 0, "ad {name} has no weight");
+            assert!(
+                parsed.end.map_or(true, |end| end >= parsed.start),
+                "ad {name} ends before it starts"
+            );
+            assert!(
+                parsed
+                    .target
+                    .strip_prefix("/ads/")
+                    .is_some_and(|slug| { landing_pages.iter().any(|page| page.id == slug) }),
+                "ad {name} links to {}, which is not a landing page",
+                parsed.target
+            );
+            format!("{} {}", data.len(), data)
+        })
+        .collect()
+}
+
+#[derive(Serialize)]
+struct Response<T> {
+    version: u32,
+    data: T,
+}
+
+fn generate_api(articles: &[Article], crosswords: &[Crossword]) {
+    create_dir_all(public("api/articles")).unwrap();
+    create_dir_all(public("api/crosswords")).unwrap();
+    write_json("api/articles.json", articles);
+    for article in articles {
+        write_json(&format!("api/articles/{}.json", article.id), article);
+    }
+    for (id, crossword) in crosswords.iter().enumerate() {
+        write_json(&format!("api/crosswords/{id}.json"), crossword);
+    }
+}
+
+fn write_json(path: &str, data: impl Serialize) {
+    serde_json::to_writer(
+        File::create(public(path)).unwrap(),
+        &Response {
+            version: API_VERSION,
+            data,
+        },
+    )
+    .unwrap();
+}
+
+fn generate_feeds(articles: &[Article]) {
+    write_feeds("", TITLE, articles);
+    for (topic, articles) in articles
+        .iter()
+        .cloned()
+        .into_group_map_by(|article| article.topic)
+    {
+        write_feeds(
+            &format!("feeds/{}/", slug(topic)),
+            &format!("{TITLE} | {topic}"),
+            &articles,
+        );
+    }
+}
+
+fn write_feeds(directory: &str, title: &str, articles: &[Article]) {
+    let directory = public(directory);
+    create_dir_all(&directory).unwrap();
+    File::create(format!("{directory}feed.xml"))
+        .unwrap()
+        .write_all(rss(&directory, title, articles).as_bytes())
+        .unwrap();
+    File::create(format!("{directory}atom.xml"))
+        .unwrap()
+        .write_all(atom(&directory, title, articles).as_bytes())
+        .unwrap();
+}
+
+fn rss(directory: &str, title: &str, articles: &[Article]) -> String {
+    let items = articles
+        .iter()
+        .map(|article| {
+            let link = format!("{SITE}/articles/{}", article.id);
+            format!(
+                "<item><title>{}</title><link>{link}</link><guid>{link}</guid>\
+                 <pubDate>{}</pubDate><category>{}</category><description>{}</description>\
+                 <media:content url=\"{}\" medium=\"image\"/>\
+                 <content:encoded>{}</content:encoded></item>",
+                escape(article.title),
+                published(article).to_rfc2822(),
+                escape(article.topic),
+                escape(article.blurb),
+                escape(&absolute(article.image.url)),
+                escape(&render(article)),
+            )
+        })
+        .join("");
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+         <rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\" \
+         xmlns:content=\"http://purl.org/rss/1.0/modules/content/\" \
+         xmlns:media=\"http://search.yahoo.com/mrss/\">\
+         <channel><title>{}</title><link>{SITE}/</link><description>{}</description>\
+         <atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>{items}</channel></rss>",
+        escape(title),
+        escape(DESCRIPTION),
+        feed_url(directory, "feed.xml"),
+    )
+}
+
+fn atom(directory: &str, title: &str, articles: &[Article]) -> String {
+    let updated = articles
+        .iter()
+        .map(published)
+        .max()
+        .unwrap_or_default()
+        .to_rfc3339();
+    let entries = articles
+        .iter()
+        .map(|article| {
+            let link = format!("{SITE}/articles/{}", article.id);
+            let published = published(article).to_rfc3339();
+            format!(
+                "<entry><title>{}</title><id>{link}</id><link href=\"{link}\"/>\
+                 <published>{published}</published><updated>{published}</updated>\
+                 <category term=\"{}\"/><summary>{}</summary>\
+                 <link rel=\"enclosure\" href=\"{}\"/>\
+                 <content type=\"html\">{}</content></entry>",
+                escape(article.title),
+                escape(article.topic),
+                escape(article.blurb),
+                escape(&absolute(article.image.url)),
+                escape(&render(article)),
+            )
+        })
+        .join("");
+    let url = feed_url(directory, "atom.xml");
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+         <feed xmlns=\"http://www.w3.org/2005/Atom\"><title>{}</title><subtitle>{}</subtitle>\
+         <id>{url}</id><link href=\"{url}\" rel=\"self\"/><link href=\"{SITE}/\"/>\
+         <updated>{updated}</updated><author><name>{TITLE}</name></author>{entries}</feed>",
+        escape(title),
+        escape(DESCRIPTION),
+    )
+}
+
+fn render(article: &Article) -> String {
+    once(&Fragment::Image(article.image.clone()))
+        .chain(&article.fragments)
+        .map(|fragment| match fragment {
+            Fragment::Text(text) => format!("<p>{}</p>", escape(text)),
+            Fragment::Image(Image { url, caption }) => format!(
+                "<figure><img src=\"{}\" alt=\"{}\"/><figcaption>{}</figcaption></figure>",
+                escape(&absolute(url)),
+                escape(caption),
+                escape(caption),
+            ),
+            Fragment::Event(id) => {
+                format!("<p><a href=\"{SITE}/events/{id}\">Event details</a></p>")
+            }
+            Fragment::Poll(Poll {
+                question, options, ..
+            }) => format!(
+                "<p><strong>{}</strong></p><ul>{}</ul>",
+                escape(question),
+                options
+                    .iter()
+                    .map(|option| format!("<li>{}</li>", escape(option)))
+                    .join(""),
+            ),
+        })
+        .join("")
+}
+
+fn published(article: &Article) -> DateTime<Utc> {
+    Utc.from_utc_datetime(&article.date.and_time(NaiveTime::MIN))
+}
+
+fn feed_url(directory: &str, file: &str) -> String {
+    format!(
+        "{SITE}/{}{file}",
+        directory.strip_prefix(&public("")).unwrap()
+    )
+}
+
+fn absolute(url: &str) -> String {
+    if url.starts_with('/') {
+        format!("{SITE}{url}")
+    } else {
+        url.to_string()
+    }
+}
+
+fn slug(text: &str) -> String {
+    text.to_lowercase().split_whitespace().join("-")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+// Trunk only copies files it knows about, so everything published next to the site is written to
+// `generated/` in the repository and copied into the output by the post-build hook in Trunk.toml.
+fn public(path: &str) -> String {
+    format!("{}/generated/{path}", var("CARGO_MANIFEST_DIR").unwrap())
+}
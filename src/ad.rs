@@ -1,9 +1,125 @@
-use itertools::Itertools;
-use lazy_static::lazy_static;
-
-lazy_static! {
-    pub static ref ADS: &'static [&'static str] = {
-        let data = include_str!(concat!(env!("OUT_DIR"), "/ads"));
-        data.lines().collect_vec().leak()
-    };
+use chrono::NaiveDate;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_until1},
+    character::complete::u32,
+    combinator::{map, map_res, rest, value},
+    sequence::{preceded, tuple},
+    IResult,
+};
+use strum::{Display, VariantArray};
+
+pub const IN_ARTICLE_EVERY: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, VariantArray)]
+#[strum(serialize_all = "lowercase")]
+pub enum Size {
+    Horizontal,
+    Vertical,
+}
+
+impl Size {
+    pub fn directory(self) -> &'static str {
+        match self {
+            Self::Horizontal => "horizontal-ads",
+            Self::Vertical => "vertical-ads",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    Horizontal,
+    Vertical,
+    InArticle,
+}
+
+impl Slot {
+    pub fn size(self) -> Size {
+        match self {
+            Self::Horizontal | Self::InArticle => Size::Horizontal,
+            Self::Vertical => Size::Vertical,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ad {
+    pub size: Size,
+    pub image: &'static str,
+    pub advertiser: &'static str,
+    pub category: &'static str,
+    pub alt: &'static str,
+    pub target: &'static str,
+    pub weight: u32,
+    pub start: NaiveDate,
+    pub end: Option<NaiveDate>,
+}
+
+impl Ad {
+    pub fn parse(input: &'static str) -> IResult<&'static str, Self> {
+        let date = |input| {
+            map_res(take_until1("\n"), |input: &str| {
+                NaiveDate::parse_from_str(input, "%Y-%m-%d")
+            })(input)
+        };
+        map(
+            tuple((
+                alt((
+                    value(Size::Horizontal, tag("horizontal")),
+                    value(Size::Vertical, tag("vertical")),
+                )),
+                preceded(tag(" "), take_until1(" ")),
+                preceded(tag(" "), take_until1("\n")),
+                preceded(tag("\n"), take_until1("\n")),
+                preceded(tag("\n"), take_until1("\n")),
+                preceded(tag("\n"), take_until1("\n")),
+                preceded(tag("\n"), u32),
+                preceded(tag("\n"), date),
+                preceded(
+                    tag("\n"),
+                    alt((
+                        value(None, tag("-")),
+                        map_res(rest, |input: &str| {
+                            NaiveDate::parse_from_str(input, "%Y-%m-%d").map(Some)
+                        }),
+                    )),
+                ),
+            )),
+            |(size, image, advertiser, category, alt, target, weight, start, end)| Self {
+                size,
+                image,
+                advertiser,
+                category,
+                alt,
+                target,
+                weight,
+                start,
+                end,
+            },
+        )(input)
+    }
+
+    pub fn src(&self) -> String {
+        format!("/images/{}/{}", self.size.directory(), self.image)
+    }
+
+    pub fn active(&self, today: NaiveDate) -> bool {
+        self.start <= today && self.end.map_or(true, |end| today <= end)
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn ad(advertiser: &'static str, category: &'static str) -> Ad {
+    Ad {
+        size: Size::Horizontal,
+        image: advertiser,
+        advertiser,
+        category,
+        alt: advertiser,
+        target: "/ads/example",
+        weight: 1,
+        start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+        end: None,
+    }
 }
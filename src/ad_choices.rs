@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ad::Ad;
+use crate::storage::Storage;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Choices {
+    pub kept: Vec<String>,
+    pub advertisers: Vec<String>,
+    pub categories: Vec<String>,
+}
+
+impl Choices {
+    pub fn allows(&self, ad: &Ad) -> bool {
+        let listed = |names: &[String], name: &str| names.iter().any(|listed| listed == name);
+        !listed(&self.advertisers, ad.advertiser)
+            && (listed(&self.kept, ad.advertiser) || !listed(&self.categories, ad.category))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.kept.is_empty() && self.advertisers.is_empty() && self.categories.is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AdChoices<S> {
+    storage: S,
+}
+
+impl<S: Storage> AdChoices<S> {
+    const KEY: &'static str = "ad-choices";
+
+    pub const fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    pub fn choices(&self) -> Choices {
+        self.storage.load(Self::KEY)
+    }
+
+    pub fn keep(&self, advertiser: &str) -> Choices {
+        self.update(|choices| {
+            choices.advertisers.retain(|hidden| hidden != advertiser);
+            add(&mut choices.kept, advertiser);
+        })
+    }
+
+    pub fn hide_advertiser(&self, advertiser: &str) -> Choices {
+        self.update(|choices| {
+            choices.kept.retain(|kept| kept != advertiser);
+            add(&mut choices.advertisers, advertiser);
+        })
+    }
+
+    pub fn hide_category(&self, category: &str) -> Choices {
+        self.update(|choices| add(&mut choices.categories, category))
+    }
+
+    pub fn restore_advertiser(&self, advertiser: &str) -> Choices {
+        self.update(|choices| {
+            choices.kept.retain(|kept| kept != advertiser);
+            choices.advertisers.retain(|hidden| hidden != advertiser);
+        })
+    }
+
+    pub fn restore_category(&self, category: &str) -> Choices {
+        self.update(|choices| choices.categories.retain(|hidden| hidden != category))
+    }
+
+    pub fn reset(&self) -> Choices {
+        self.storage.remove(Self::KEY);
+        Choices::default()
+    }
+
+    fn update(&self, f: impl FnOnce(&mut Choices)) -> Choices {
+        let mut choices = self.choices();
+        f(&mut choices);
+        self.storage.save(Self::KEY, &choices);
+        choices
+    }
+}
+
+fn add(names: &mut Vec<String>, name: &str) {
+    if !names.iter().any(|existing| existing == name) {
+        names.push(name.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AdChoices, Choices};
+    use crate::{ad::ad, storage::MemoryStorage};
+
+    #[test]
+    fn allows_everything_by_default() {
+        assert!(Choices::default().allows(&ad("Rob's", "Finance")));
+    }
+
+    #[test]
+    fn hidden_advertisers_and_categories_are_not_allowed() {
+        let choices = AdChoices::new(MemoryStorage::default());
+        choices.hide_advertiser("Rob's");
+        let choices = choices.hide_category("Funerals");
+        assert!(!choices.allows(&ad("Rob's", "Finance")));
+        assert!(!choices.allows(&ad("Fun Family", "Funerals")));
+        assert!(choices.allows(&ad("Wash It", "Plumbing")));
+    }
+
+    #[test]
+    fn kept_advertisers_survive_hidden_categories() {
+        let choices = AdChoices::new(MemoryStorage::default());
+        choices.keep("Fun Family");
+        let choices = choices.hide_category("Funerals");
+        assert!(choices.allows(&ad("Fun Family", "Funerals")));
+        assert!(!choices.allows(&ad("Grim Tidings", "Funerals")));
+    }
+
+    #[test]
+    fn keeping_and_hiding_replace_each_other() {
+        let choices = AdChoices::new(MemoryStorage::default());
+        choices.hide_advertiser("Rob's");
+        let kept = choices.keep("Rob's");
+        assert_eq!(kept.kept, ["Rob's"]);
+        assert!(kept.advertisers.is_empty());
+        let hidden = choices.hide_advertiser("Rob's");
+        assert!(hidden.kept.is_empty());
+        assert_eq!(hidden.advertisers, ["Rob's"]);
+    }
+
+    #[test]
+    fn restoring_undoes_choices() {
+        let storage = MemoryStorage::default();
+        let choices = AdChoices::new(storage.clone());
+        choices.keep("Fun Family");
+        choices.hide_advertiser("Rob's");
+        choices.hide_category("Plumbing");
+        choices.restore_advertiser("Fun Family");
+        choices.restore_advertiser("Rob's");
+        choices.restore_category("Plumbing");
+        assert!(AdChoices::new(storage).choices().is_empty());
+    }
+
+    #[test]
+    fn reset_clears_storage() {
+        let storage = MemoryStorage::default();
+        let choices = AdChoices::new(storage.clone());
+        choices.hide_category("Plumbing");
+        assert!(choices.reset().is_empty());
+        assert!(AdChoices::new(storage).choices().is_empty());
+    }
+}
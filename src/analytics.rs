@@ -0,0 +1,199 @@
+use std::{cell::RefCell, mem::take, rc::Rc};
+
+use leptos::{logging::log, window};
+use serde::{Deserialize, Serialize};
+
+pub const BATCH_SIZE: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AnalyticsEvent {
+    PageView { path: String },
+    ReadDepth { article: String, progress: f64 },
+    CrosswordCompleted { crossword: usize },
+    AdImpression { advertiser: String, image: String },
+    AdClick { advertiser: String, image: String },
+}
+
+pub trait AnalyticsSink {
+    fn record(&self, event: AnalyticsEvent);
+
+    fn flush(&self) {}
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConsoleSink;
+
+impl AnalyticsSink for ConsoleSink {
+    fn record(&self, event: AnalyticsEvent) {
+        log!("{}", serde_json::to_string(&event).unwrap());
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MemorySink(Rc<RefCell<Vec<AnalyticsEvent>>>);
+
+impl MemorySink {
+    pub fn events(&self) -> Vec<AnalyticsEvent> {
+        self.0.borrow().clone()
+    }
+}
+
+impl AnalyticsSink for MemorySink {
+    fn record(&self, event: AnalyticsEvent) {
+        self.0.borrow_mut().push(event);
+    }
+}
+
+pub trait Transport {
+    fn send(&self, url: &str, body: &str);
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Beacon;
+
+impl Transport for Beacon {
+    fn send(&self, url: &str, body: &str) {
+        _ = window()
+            .navigator()
+            .send_beacon_with_opt_str(url, Some(body));
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpSink<T = Beacon> {
+    base: String,
+    batch_size: usize,
+    pending: Rc<RefCell<Vec<AnalyticsEvent>>>,
+    transport: T,
+}
+
+impl<T: Transport> HttpSink<T> {
+    pub fn new(base: impl Into<String>, batch_size: usize, transport: T) -> Self {
+        Self {
+            base: base.into(),
+            batch_size,
+            pending: Rc::default(),
+            transport,
+        }
+    }
+}
+
+impl Default for HttpSink {
+    fn default() -> Self {
+        Self::new(env!("ANALYTICS_API"), BATCH_SIZE, Beacon)
+    }
+}
+
+impl<T: Transport> AnalyticsSink for HttpSink<T> {
+    fn record(&self, event: AnalyticsEvent) {
+        let full = {
+            let mut pending = self.pending.borrow_mut();
+            pending.push(event);
+            pending.len() >= self.batch_size
+        };
+        if full {
+            self.flush();
+        }
+    }
+
+    fn flush(&self) {
+        let batch = take(&mut *self.pending.borrow_mut());
+        if batch.is_empty() {
+            return;
+        }
+        self.transport.send(
+            &format!("{}/events", self.base),
+            &serde_json::to_string(&batch).unwrap(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use serde_json::json;
+
+    use super::{AnalyticsEvent, AnalyticsSink, HttpSink, MemorySink, Transport};
+
+    #[derive(Debug, Clone, Default)]
+    struct Sent(Rc<RefCell<Vec<(String, String)>>>);
+
+    impl Transport for Sent {
+        fn send(&self, url: &str, body: &str) {
+            self.0
+                .borrow_mut()
+                .push((url.to_string(), body.to_string()));
+        }
+    }
+
+    fn page_view(path: &str) -> AnalyticsEvent {
+        AnalyticsEvent::PageView {
+            path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn serializes_with_a_kind_tag() {
+        assert_eq!(
+            serde_json::to_value(page_view("/")).unwrap(),
+            json!({ "kind": "page_view", "path": "/" })
+        );
+        assert_eq!(
+            serde_json::to_value(AnalyticsEvent::ReadDepth {
+                article: "budget".to_string(),
+                progress: 0.5,
+            })
+            .unwrap(),
+            json!({ "kind": "read_depth", "article": "budget", "progress": 0.5 })
+        );
+        assert_eq!(
+            serde_json::to_value(AnalyticsEvent::AdClick {
+                advertiser: "Rob's".to_string(),
+                image: "/ad.png".to_string(),
+            })
+            .unwrap(),
+            json!({ "kind": "ad_click", "advertiser": "Rob's", "image": "/ad.png" })
+        );
+    }
+
+    #[test]
+    fn flushes_a_full_batch() {
+        let sent = Sent::default();
+        let sink = HttpSink::new("https://example.com", 2, sent.clone());
+        sink.record(page_view("/"));
+        assert!(sent.0.borrow().is_empty());
+        sink.record(page_view("/events"));
+        let batch: Vec<AnalyticsEvent> = {
+            let sent = sent.0.borrow();
+            assert_eq!(sent.len(), 1);
+            assert_eq!(sent[0].0, "https://example.com/events");
+            serde_json::from_str(&sent[0].1).unwrap()
+        };
+        assert_eq!(batch, [page_view("/"), page_view("/events")]);
+    }
+
+    #[test]
+    fn flushes_what_is_pending() {
+        let sent = Sent::default();
+        let sink = HttpSink::new("", 10, sent.clone());
+        sink.flush();
+        assert!(sent.0.borrow().is_empty());
+        sink.record(page_view("/"));
+        sink.flush();
+        sink.flush();
+        assert_eq!(sent.0.borrow().len(), 1);
+        assert_eq!(sent.0.borrow()[0].0, "/events");
+    }
+
+    #[test]
+    fn memory_sink_keeps_events_in_order() {
+        let sink = MemorySink::default();
+        let recorder: &dyn AnalyticsSink = &sink.clone();
+        recorder.record(page_view("/"));
+        recorder.record(page_view("/events"));
+        recorder.flush();
+        assert_eq!(sink.events(), [page_view("/"), page_view("/events")]);
+    }
+}
@@ -1,38 +1,51 @@
-use anyhow::{anyhow, Result};
-use lazy_static::lazy_static;
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, NaiveDate};
 use nom::{
     branch::alt,
     bytes::complete::{tag, take_until1},
-    combinator::{all_consuming, map, map_res, rest},
+    combinator::{map, map_res, rest},
     error::Error,
-    multi::{length_data, separated_list1},
+    multi::{length_data, many1, separated_list1},
     sequence::{pair, preceded, terminated, tuple},
     IResult,
 };
+use serde::Serialize;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct Article {
     pub id: &'static str,
     pub topic: &'static str,
     pub index: usize,
+    pub date: NaiveDate,
     pub blurb: &'static str,
     pub title: &'static str,
     pub image: Image,
     pub fragments: Vec<Fragment>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", content = "content", rename_all = "lowercase")]
 pub enum Fragment {
     Text(&'static str),
     Image(Image),
+    Poll(Poll),
+    Event(&'static str),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct Image {
     pub url: &'static str,
     pub caption: &'static str,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Poll {
+    pub id: &'static str,
+    pub question: &'static str,
+    pub options: Vec<&'static str>,
+}
+
 impl Fragment {
     pub const fn as_text(&self) -> Option<&&'static str> {
         if let Self::Text(v) = self {
@@ -56,6 +69,12 @@ impl Article {
                     tag(" "),
                     map_res(take_until1("\n"), |input: &str| input.parse::<usize>()),
                 ),
+                preceded(
+                    tag("\n"),
+                    map_res(take_until1("\n"), |input: &str| {
+                        NaiveDate::parse_from_str(input, "%Y-%m-%d")
+                    }),
+                ),
                 preceded(tag("\n"), take_until1("\n")),
                 preceded(tag("\n"), take_until1("\n")),
                 map(
@@ -77,15 +96,34 @@ impl Article {
                                 )),
                                 |(url, caption)| Fragment::Image(Image { url, caption }),
                             ),
+                            map(
+                                tuple((
+                                    preceded(tag("poll "), take_until1("\n")),
+                                    preceded(tag("\n"), take_until1("\n")),
+                                    many1(preceded(tag("\n- "), alt((take_until1("\n"), rest)))),
+                                )),
+                                |(id, question, options)| {
+                                    Fragment::Poll(Poll {
+                                        id,
+                                        question,
+                                        options,
+                                    })
+                                },
+                            ),
+                            map(
+                                preceded(tag("event "), alt((take_until1("\n"), rest))),
+                                Fragment::Event,
+                            ),
                             map(alt((take_until1("\n\n"), rest)), Fragment::Text),
                         )),
                     ),
                 ),
             )),
-            |(topic, id, index, title, blurb, image, fragments)| Self {
+            |(topic, id, index, date, title, blurb, image, fragments)| Self {
                 id,
                 topic,
                 index,
+                date,
                 blurb,
                 title,
                 image,
@@ -111,24 +149,70 @@ impl Article {
     }
 }
 
-lazy_static! {
-    pub static ref ARTICLES: &'static [Article] = (|| -> Result<&'static [Article]> {
-        let mut data = include_str!(concat!(env!("OUT_DIR"), "/articles"));
-        let mut articles = Vec::new();
-        while !data.is_empty() {
-            let (length, rest) = data
-                .split_once(' ')
-                .ok_or_else(|| anyhow!("invalid data"))?;
-            let length: usize = length.parse()?;
-            let (_, article) = all_consuming(Article::parse)(&rest[..length])?;
-            articles.push(article);
-            data = rest
-                .get(length + 1..)
-                .or_else(|| rest.get(length..))
-                .unwrap();
+pub const ARCHIVE_PAGE_SIZE: usize = 6;
+
+pub fn archive(articles: &[Article]) -> BTreeMap<(i32, u32), Vec<&Article>> {
+    let mut months = BTreeMap::<_, Vec<_>>::new();
+    for article in articles {
+        months
+            .entry((article.date.year(), article.date.month()))
+            .or_default()
+            .push(article);
+    }
+    months
+}
+
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b
+        .chars()
+        .map(|char| char.to_ascii_lowercase())
+        .collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().map(|char| char.to_ascii_lowercase()).enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            current.push(
+                (previous[j] + usize::from(a != *b))
+                    .min(previous[j + 1] + 1)
+                    .min(current[j] + 1),
+            );
         }
-        articles.sort_unstable_by_key(|article| -(article.index as i128));
-        Ok(articles.leak())
-    })()
-    .unwrap();
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::edit_distance;
+
+    #[test]
+    fn identical_strings_have_no_distance() {
+        assert_eq!(edit_distance("egg-prices", "egg-prices"), 0);
+        assert_eq!(edit_distance("", ""), 0);
+    }
+
+    #[test]
+    fn ignores_case() {
+        assert_eq!(edit_distance("Egg-Prices", "egg-prices"), 0);
+        assert_eq!(edit_distance("EGG", "egs"), 1);
+    }
+
+    #[test]
+    fn counts_insertions_deletions_and_substitutions() {
+        assert_eq!(edit_distance("", "egg"), 3);
+        assert_eq!(edit_distance("egg", ""), 3);
+        assert_eq!(edit_distance("eg", "egg"), 1);
+        assert_eq!(edit_distance("eggs", "egg"), 1);
+        assert_eq!(edit_distance("egg", "ego"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn is_symmetric() {
+        assert_eq!(
+            edit_distance("solar-powered-lights", "solar-lights"),
+            edit_distance("solar-lights", "solar-powered-lights")
+        );
+    }
 }
@@ -0,0 +1,86 @@
+use std::{
+    env::args,
+    fs::OpenOptions,
+    io::{Cursor, Write},
+    path::PathBuf,
+};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use the_waratah::analytics::AnalyticsEvent;
+use tiny_http::{Header, Method, Response, Server};
+
+#[derive(Serialize)]
+struct Received {
+    received: DateTime<Utc>,
+    #[serde(flatten)]
+    event: AnalyticsEvent,
+}
+
+fn main() {
+    let address = args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:3002".to_string());
+    let path = PathBuf::from(
+        args()
+            .nth(2)
+            .unwrap_or_else(|| "analytics.ndjson".to_string()),
+    );
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .unwrap();
+    let server = Server::http(&address).unwrap();
+    println!(
+        "collecting analytics on http://{address}, written to {}",
+        path.display()
+    );
+    for mut request in server.incoming_requests() {
+        let response = match (request.method(), request.url().trim_matches('/')) {
+            (Method::Options, _) => respond(204),
+            (Method::Post, "events") => {
+                let mut body = String::new();
+                let events = request
+                    .as_reader()
+                    .read_to_string(&mut body)
+                    .ok()
+                    .and_then(|_| serde_json::from_str::<Vec<AnalyticsEvent>>(&body).ok());
+                match events {
+                    Some(events) => {
+                        let received = Utc::now();
+                        let written = events.into_iter().try_for_each(|event| {
+                            let line =
+                                serde_json::to_string(&Received { received, event }).unwrap();
+                            writeln!(file, "{line}")
+                        });
+                        match written {
+                            Ok(()) => respond(204),
+                            Err(error) => {
+                                eprintln!("failed to write to {}: {error}", path.display());
+                                respond(500)
+                            }
+                        }
+                    }
+                    None => respond(400),
+                }
+            }
+            _ => respond(404),
+        };
+        _ = request.respond(response);
+    }
+}
+
+fn respond(status: u16) -> Response<Cursor<Vec<u8>>> {
+    [
+        "Access-Control-Allow-Origin: *",
+        "Access-Control-Allow-Methods: POST, OPTIONS",
+        "Access-Control-Allow-Headers: Content-Type",
+    ]
+    .into_iter()
+    .map(|header| header.parse::<Header>().unwrap())
+    .fold(
+        Response::from_string("").with_status_code(status),
+        Response::with_header,
+    )
+}
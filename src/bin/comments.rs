@@ -0,0 +1,101 @@
+use std::{
+    env::args,
+    fs::{read_to_string, write},
+    io::Cursor,
+    path::PathBuf,
+};
+
+use chrono::Utc;
+use serde::Serialize;
+use the_waratah::comments::{CommentError, CommentStore, NewComment};
+use tiny_http::{Header, Method, Response, Server};
+
+fn main() {
+    let address = args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:3001".to_string());
+    let path = PathBuf::from(args().nth(2).unwrap_or_else(|| "comments.json".to_string()));
+    let mut store: CommentStore = read_to_string(&path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    let server = Server::http(&address).unwrap();
+    println!(
+        "serving comments on http://{address}, stored in {}",
+        path.display()
+    );
+    for mut request in server.incoming_requests() {
+        let client = request
+            .remote_addr()
+            .map(|address| address.ip().to_string())
+            .unwrap_or_default();
+        let segments = request
+            .url()
+            .trim_matches('/')
+            .split('/')
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        let segments = segments.iter().map(String::as_str).collect::<Vec<_>>();
+        let response = match (request.method(), segments.as_slice()) {
+            (Method::Options, _) => respond(204, &()),
+            (Method::Get, ["comments", article]) => respond(200, &store.list(article)),
+            (Method::Post, ["comments", article]) => {
+                let mut body = String::new();
+                let comment = request
+                    .as_reader()
+                    .read_to_string(&mut body)
+                    .ok()
+                    .and_then(|_| serde_json::from_str::<NewComment>(&body).ok());
+                match comment.map(|comment| store.post(article, comment, &client, Utc::now())) {
+                    Some(Ok(comment)) => {
+                        save(&store, &path);
+                        respond(201, &comment)
+                    }
+                    Some(Err(error)) => failure(&error),
+                    None => failure(&CommentError::Invalid("Malformed comment.".to_string())),
+                }
+            }
+            (Method::Post, ["flags", id]) => match id.parse().map(|id| store.flag(id, &client)) {
+                Ok(Ok(())) => {
+                    save(&store, &path);
+                    respond(200, &())
+                }
+                Ok(Err(error)) => failure(&error),
+                Err(_) => failure(&CommentError::NotFound),
+            },
+            _ => failure(&CommentError::NotFound),
+        };
+        let _ = request.respond(response);
+    }
+}
+
+fn save(store: &CommentStore, path: &PathBuf) {
+    write(path, serde_json::to_string_pretty(store).unwrap()).unwrap();
+}
+
+fn failure(error: &CommentError) -> Response<Cursor<Vec<u8>>> {
+    respond(
+        match error {
+            CommentError::Invalid(_) => 400,
+            CommentError::NotFound => 404,
+            CommentError::RateLimited => 429,
+            CommentError::Unavailable(_) => 503,
+        },
+        error,
+    )
+}
+
+fn respond(status: u16, body: &impl Serialize) -> Response<Cursor<Vec<u8>>> {
+    [
+        "Content-Type: application/json",
+        "Access-Control-Allow-Origin: *",
+        "Access-Control-Allow-Methods: GET, POST, OPTIONS",
+        "Access-Control-Allow-Headers: Content-Type",
+    ]
+    .into_iter()
+    .map(|header| header.parse::<Header>().unwrap())
+    .fold(
+        Response::from_string(serde_json::to_string(body).unwrap()).with_status_code(status),
+        Response::with_header,
+    )
+}
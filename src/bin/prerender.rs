@@ -0,0 +1,55 @@
+use std::{
+    env::args,
+    fs::{create_dir_all, read_to_string, write},
+    path::Path,
+};
+
+use leptos::{create_runtime, leptos_dom::HydrationCtx, provide_context, view, IntoView};
+use leptos_meta::{generate_head_metadata_separated, provide_meta_context};
+use leptos_router::{RouterIntegrationContext, ServerIntegration};
+use the_waratah::{
+    components::App,
+    content::{ARTICLES, CROSSWORDS, EVENTS, LANDING_PAGES},
+    routes::pages,
+};
+
+fn main() {
+    let dist = args().nth(1).unwrap_or_else(|| "dist".to_string());
+    let dist = Path::new(&dist);
+    let template = read_to_string(dist.join("index.html"))
+        .expect("run `trunk build --no-default-features --features hydrate` first");
+    let (head, rest) = template.split_once("</head>").unwrap();
+    let (body, rest) = rest.split_once('>').unwrap();
+    let (content, tail) = rest.split_once("</body>").unwrap();
+    let routes = pages(&ARTICLES, CROSSWORDS.len(), &EVENTS, &LANDING_PAGES)
+        .into_iter()
+        .map(|page| page.path)
+        .chain(["/404".to_string()]);
+    for route in routes {
+        let (meta, html) = render(&route);
+        let path = match route.as_str() {
+            "/" => dist.join("index.html"),
+            route => dist.join(format!("{}.html", route.trim_start_matches('/'))),
+        };
+        create_dir_all(path.parent().unwrap()).unwrap();
+        write(
+            &path,
+            format!("{head}{meta}</head>{body}>{html}{content}</body>{tail}"),
+        )
+        .unwrap();
+        println!("{route} -> {}", path.display());
+    }
+}
+
+fn render(route: &str) -> (String, String) {
+    let runtime = create_runtime();
+    HydrationCtx::reset_id();
+    provide_context(RouterIntegrationContext::new(ServerIntegration {
+        path: format!("{}{route}", env!("SITE")),
+    }));
+    provide_meta_context();
+    let html = view! { <App /> }.into_view().render_to_string().to_string();
+    let (meta, _) = generate_head_metadata_separated();
+    runtime.dispose();
+    (meta, html)
+}
@@ -0,0 +1,83 @@
+use crate::storage::Storage;
+
+#[derive(Debug, Clone)]
+pub struct Bookmarks<S> {
+    storage: S,
+}
+
+impl<S: Storage> Bookmarks<S> {
+    const KEY: &'static str = "bookmarks";
+
+    pub const fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    pub fn ids(&self) -> Vec<String> {
+        self.storage.load(Self::KEY)
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.ids().iter().any(|saved| saved == id)
+    }
+
+    pub fn toggle(&self, id: &str) -> bool {
+        let mut ids = self.ids();
+        let saved = if let Some(index) = ids.iter().position(|saved| saved == id) {
+            ids.remove(index);
+            false
+        } else {
+            ids.push(id.to_string());
+            true
+        };
+        self.storage.save(Self::KEY, &ids);
+        saved
+    }
+
+    pub fn remove(&self, id: &str) {
+        let mut ids = self.ids();
+        ids.retain(|saved| saved != id);
+        self.storage.save(Self::KEY, &ids);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Bookmarks;
+    use crate::storage::MemoryStorage;
+
+    #[test]
+    fn toggle_saves_and_unsaves() {
+        let bookmarks = Bookmarks::new(MemoryStorage::default());
+        assert!(!bookmarks.contains("egg-prices"));
+        assert!(bookmarks.toggle("egg-prices"));
+        assert!(bookmarks.contains("egg-prices"));
+        assert!(!bookmarks.toggle("egg-prices"));
+        assert!(!bookmarks.contains("egg-prices"));
+    }
+
+    #[test]
+    fn keeps_the_order_articles_were_saved_in() {
+        let bookmarks = Bookmarks::new(MemoryStorage::default());
+        bookmarks.toggle("b");
+        bookmarks.toggle("a");
+        bookmarks.toggle("c");
+        bookmarks.remove("a");
+        bookmarks.toggle("a");
+        assert_eq!(bookmarks.ids(), ["b", "c", "a"]);
+    }
+
+    #[test]
+    fn persists_through_storage() {
+        let storage = MemoryStorage::default();
+        Bookmarks::new(storage.clone()).toggle("egg-prices");
+        assert_eq!(Bookmarks::new(storage).ids(), ["egg-prices"]);
+    }
+
+    #[test]
+    fn removing_a_missing_id_does_nothing() {
+        let bookmarks = Bookmarks::new(MemoryStorage::default());
+        bookmarks.toggle("a");
+        bookmarks.remove("b");
+        assert_eq!(bookmarks.ids(), ["a"]);
+    }
+}
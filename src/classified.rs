@@ -0,0 +1,93 @@
+use std::fmt::{self, Display, Formatter};
+
+use chrono::NaiveDate;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_until1},
+    character::complete::u32,
+    combinator::{map, map_res, rest, value},
+    error::Error,
+    multi::length_data,
+    sequence::{preceded, terminated, tuple},
+    IResult,
+};
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Classified {
+    pub id: &'static str,
+    pub category: &'static str,
+    pub posted: NaiveDate,
+    pub expires: NaiveDate,
+    pub price: Option<Price>,
+    pub contact: &'static str,
+    pub title: &'static str,
+    pub body: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", content = "dollars", rename_all = "lowercase")]
+pub enum Price {
+    Dollars(u32),
+    Free,
+    Offers,
+}
+
+impl Display for Price {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Dollars(dollars) => write!(f, "${dollars}"),
+            Self::Free => write!(f, "Free"),
+            Self::Offers => write!(f, "Offers"),
+        }
+    }
+}
+
+impl Classified {
+    pub fn parse(input: &'static str) -> IResult<&'static str, Self> {
+        let date = |input| {
+            map_res(take_until1("\n"), |input: &str| {
+                NaiveDate::parse_from_str(input, "%Y-%m-%d")
+            })(input)
+        };
+        map(
+            tuple((
+                length_data(map_res(
+                    terminated(take_until1::<_, _, Error<&str>>(" "), tag(" ")),
+                    |input: &str| input.parse::<usize>(),
+                )),
+                preceded(tag(" "), take_until1(" ")),
+                preceded(tag(" "), date),
+                preceded(tag("\n"), date),
+                preceded(
+                    tag("\n"),
+                    alt((
+                        map(preceded(tag("$"), u32), |dollars| {
+                            Some(Price::Dollars(dollars))
+                        }),
+                        value(Some(Price::Free), tag("free")),
+                        value(Some(Price::Offers), tag("offers")),
+                        value(None, tag("-")),
+                    )),
+                ),
+                preceded(tag("\n"), take_until1("\n")),
+                preceded(tag("\n"), take_until1("\n")),
+                preceded(tag("\n"), rest),
+            )),
+            |(category, id, posted, expires, price, contact, title, body)| Self {
+                id,
+                category,
+                posted,
+                expires,
+                price,
+                contact,
+                title,
+                body,
+            },
+        )(input)
+    }
+
+    pub fn expired(&self, today: NaiveDate) -> bool {
+        self.expires < today
+    }
+}
@@ -0,0 +1,300 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display, Formatter},
+    future::Future,
+};
+
+use chrono::{DateTime, Duration, Utc};
+use gloo_net::http::{Request, Response};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+pub const MAX_AUTHOR_LENGTH: usize = 40;
+pub const MAX_BODY_LENGTH: usize = 2000;
+pub const FLAG_THRESHOLD: u32 = 3;
+pub const RATE_LIMIT: usize = 3;
+pub const RATE_LIMIT_WINDOW: Duration = Duration::minutes(1);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: u64,
+    pub article: String,
+    pub parent: Option<u64>,
+    pub author: String,
+    pub body: String,
+    pub posted: DateTime<Utc>,
+    pub flags: u32,
+}
+
+impl Comment {
+    pub const fn hidden(&self) -> bool {
+        self.flags >= FLAG_THRESHOLD
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NewComment {
+    pub parent: Option<u64>,
+    pub author: String,
+    pub body: String,
+}
+
+impl NewComment {
+    pub fn validate(&self) -> Result<(), CommentError> {
+        let (author, body) = (self.author.trim(), self.body.trim());
+        if author.is_empty() {
+            Err(CommentError::Invalid("Please enter a name.".to_string()))
+        } else if author.chars().count() > MAX_AUTHOR_LENGTH {
+            Err(CommentError::Invalid(format!(
+                "Names can be at most {MAX_AUTHOR_LENGTH} characters."
+            )))
+        } else if body.is_empty() {
+            Err(CommentError::Invalid(
+                "Comments can't be empty.".to_string(),
+            ))
+        } else if body.chars().count() > MAX_BODY_LENGTH {
+            Err(CommentError::Invalid(format!(
+                "Comments can be at most {MAX_BODY_LENGTH} characters."
+            )))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommentError {
+    Invalid(String),
+    NotFound,
+    RateLimited,
+    Unavailable(String),
+}
+
+impl Display for CommentError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Invalid(reason) => write!(f, "{reason}"),
+            Self::NotFound => write!(f, "That comment no longer exists."),
+            Self::RateLimited => write!(f, "You're commenting too quickly. Try again in a minute."),
+            Self::Unavailable(reason) => {
+                write!(f, "Comments are unavailable right now ({reason}).")
+            }
+        }
+    }
+}
+
+pub trait CommentBackend {
+    fn list(&self, article: &str) -> impl Future<Output = Result<Vec<Comment>, CommentError>>;
+    fn post(
+        &self,
+        article: &str,
+        comment: NewComment,
+    ) -> impl Future<Output = Result<Comment, CommentError>>;
+    fn flag(&self, id: u64) -> impl Future<Output = Result<(), CommentError>>;
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpComments {
+    base: String,
+}
+
+impl HttpComments {
+    pub fn new(base: impl Into<String>) -> Self {
+        Self { base: base.into() }
+    }
+
+    async fn parse<T: DeserializeOwned>(response: Response) -> Result<T, CommentError> {
+        let unavailable = |error: gloo_net::Error| CommentError::Unavailable(error.to_string());
+        if response.ok() {
+            response.json().await.map_err(unavailable)
+        } else {
+            Err(response.json().await.map_err(unavailable)?)
+        }
+    }
+}
+
+impl Default for HttpComments {
+    fn default() -> Self {
+        Self::new(env!("COMMENTS_API"))
+    }
+}
+
+impl CommentBackend for HttpComments {
+    async fn list(&self, article: &str) -> Result<Vec<Comment>, CommentError> {
+        let response = Request::get(&format!("{}/comments/{article}", self.base))
+            .send()
+            .await
+            .map_err(|error| CommentError::Unavailable(error.to_string()))?;
+        Self::parse(response).await
+    }
+
+    async fn post(&self, article: &str, comment: NewComment) -> Result<Comment, CommentError> {
+        comment.validate()?;
+        let response = Request::post(&format!("{}/comments/{article}", self.base))
+            .json(&comment)
+            .map_err(|error| CommentError::Unavailable(error.to_string()))?
+            .send()
+            .await
+            .map_err(|error| CommentError::Unavailable(error.to_string()))?;
+        Self::parse(response).await
+    }
+
+    async fn flag(&self, id: u64) -> Result<(), CommentError> {
+        let response = Request::post(&format!("{}/flags/{id}", self.base))
+            .send()
+            .await
+            .map_err(|error| CommentError::Unavailable(error.to_string()))?;
+        Self::parse(response).await
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommentStore {
+    comments: Vec<Comment>,
+    #[serde(default)]
+    flaggers: HashMap<u64, HashSet<String>>,
+    #[serde(skip)]
+    recent: HashMap<String, Vec<DateTime<Utc>>>,
+}
+
+impl CommentStore {
+    pub fn list(&self, article: &str) -> Vec<Comment> {
+        self.comments
+            .iter()
+            .filter(|comment| comment.article == article)
+            .cloned()
+            .map(|comment| {
+                if comment.hidden() {
+                    Comment {
+                        body: String::new(),
+                        ..comment
+                    }
+                } else {
+                    comment
+                }
+            })
+            .collect()
+    }
+
+    pub fn post(
+        &mut self,
+        article: &str,
+        comment: NewComment,
+        client: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Comment, CommentError> {
+        comment.validate()?;
+        if comment.parent.is_some_and(|parent| {
+            !self
+                .comments
+                .iter()
+                .any(|comment| comment.id == parent && comment.article == article)
+        }) {
+            return Err(CommentError::NotFound);
+        }
+        let recent = self.recent.entry(client.to_string()).or_default();
+        recent.retain(|posted| now - *posted < RATE_LIMIT_WINDOW);
+        if recent.len() >= RATE_LIMIT {
+            return Err(CommentError::RateLimited);
+        }
+        recent.push(now);
+        let comment = Comment {
+            id: self
+                .comments
+                .iter()
+                .map(|comment| comment.id)
+                .max()
+                .unwrap_or(0)
+                + 1,
+            article: article.to_string(),
+            parent: comment.parent,
+            author: comment.author.trim().to_string(),
+            body: comment.body.trim().to_string(),
+            posted: now,
+            flags: 0,
+        };
+        self.comments.push(comment.clone());
+        Ok(comment)
+    }
+
+    pub fn flag(&mut self, id: u64, client: &str) -> Result<(), CommentError> {
+        let comment = self
+            .comments
+            .iter_mut()
+            .find(|comment| comment.id == id)
+            .ok_or(CommentError::NotFound)?;
+        let flaggers = self.flaggers.entry(id).or_default();
+        flaggers.insert(client.to_string());
+        comment.flags = u32::try_from(flaggers.len()).unwrap_or(u32::MAX);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn comment(body: &str) -> NewComment {
+        NewComment {
+            parent: None,
+            author: "Reader".to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(1_700_000_000 + seconds, 0).unwrap()
+    }
+
+    #[test]
+    fn rate_limits_each_client_within_the_window() {
+        let mut store = CommentStore::default();
+        for seconds in 0..RATE_LIMIT as i64 {
+            assert!(store.post("a", comment("hi"), "one", at(seconds)).is_ok());
+        }
+        assert_eq!(
+            store.post("a", comment("hi"), "one", at(30)),
+            Err(CommentError::RateLimited)
+        );
+        assert!(store.post("a", comment("hi"), "two", at(30)).is_ok());
+        assert!(store.post("a", comment("hi"), "one", at(60)).is_ok());
+    }
+
+    #[test]
+    fn hides_flagged_comments() {
+        let mut store = CommentStore::default();
+        let posted = store.post("a", comment("rude"), "one", at(0)).unwrap();
+        for client in 1..FLAG_THRESHOLD {
+            store.flag(posted.id, &client.to_string()).unwrap();
+        }
+        assert_eq!(store.list("a")[0].body, "rude");
+        store.flag(posted.id, "last").unwrap();
+        assert_eq!(store.list("a")[0].body, "");
+        assert_eq!(
+            store.flag(posted.id + 1, "one"),
+            Err(CommentError::NotFound)
+        );
+    }
+
+    #[test]
+    fn counts_each_flagger_once() {
+        let mut store = CommentStore::default();
+        let posted = store.post("a", comment("fine"), "one", at(0)).unwrap();
+        for _ in 0..FLAG_THRESHOLD {
+            store.flag(posted.id, "two").unwrap();
+        }
+        assert_eq!(store.list("a")[0].flags, 1);
+        assert_eq!(store.list("a")[0].body, "fine");
+    }
+
+    #[test]
+    fn lists_only_the_article() {
+        let mut store = CommentStore::default();
+        store.post("a", comment("first"), "one", at(0)).unwrap();
+        store.post("b", comment("second"), "one", at(1)).unwrap();
+        let listed = store.list("b");
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].body, "second");
+    }
+}
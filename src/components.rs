@@ -1,104 +1,152 @@
 use crate::crossword::{Crossword, Direction, Vec2, Word};
 use itertools::Itertools;
-use leptos::ev::{keydown, scroll, KeyboardEvent, MouseEvent};
+use leptos::ev::{keydown, mousedown, pagehide, scroll, KeyboardEvent, MouseEvent, SubmitEvent};
 use leptos::leptos_dom::helpers::location;
-use leptos::web_sys::HtmlButtonElement;
-use leptos_meta::{provide_meta_context, Meta};
+use leptos_meta::{provide_meta_context, Link, Meta, Script, Title};
 use std::collections::HashMap;
 use std::iter::from_fn;
 use std::iter::once;
 use std::ops::{Index, Neg, Not};
+use std::rc::Rc;
 use std::str::FromStr;
 
-use crate::ad::ADS;
-use crate::article::{Article, ARTICLES};
-use crate::article::{Fragment, Image};
-use crate::crossword::CROSSWORDS;
-use chrono::Local;
+use crate::ad::{Ad, Size, Slot, IN_ARTICLE_EVERY};
+use crate::ad_choices::{AdChoices, Choices};
+use crate::analytics::{AnalyticsEvent, AnalyticsSink, ConsoleSink, HttpSink};
+use crate::article::{archive, edit_distance, Article, ARCHIVE_PAGE_SIZE};
+use crate::article::{Fragment, Image, Poll};
+use crate::bookmarks::Bookmarks;
+use crate::comments::{Comment, CommentBackend, CommentError, HttpComments, NewComment};
+use crate::content::{find_article, ARTICLES};
+use crate::content::{ADS, CLASSIFIEDS, CROSSWORDS, EVENTS, LANDING_PAGES};
+use crate::event::Event;
+use crate::history::{Entry, History};
+use crate::horoscope::{horoscope, Sign};
+use crate::paywall::{Paywall, METER};
+use crate::polls::{Ballots, LocalPolls, PollBackend};
+use crate::preferences::{Font, Preferences, TextSize, Theme};
+use crate::rotation::{pick, Impressions};
+use crate::routes::{self, event_months, Screen, ROUTES};
+use crate::share::{encode, intents, quote_url, Intent};
+use crate::storage::{LocalStorage, SessionStorage};
+use crate::subscriptions::{
+    Field, LocalSubscriptions, Pending, SignUp, Subscription, SubscriptionBackend, Tier,
+};
+use chrono::{Datelike, Days, Local, NaiveDate};
+use leptos::window;
+use strum::VariantArray;
 
 use leptos::{
-    component, create_memo, create_signal, document, event_target, view, window_event_listener,
-    Callback, Children, CollectView, IntoView, Params, SignalGet, SignalWith,
+    component, create_effect, create_local_resource, create_memo, create_rw_signal, create_signal,
+    document, event_target, event_target_checked, event_target_value, expect_context, on_cleanup,
+    provide_context, spawn_local, store_value, view, window_event_listener, Callback, Children,
+    CollectView, IntoView, MaybeSignal, Params, ReadSignal, RwSignal, Signal, SignalGet,
+    SignalGetUntracked, SignalSet, SignalUpdate, SignalWith, SignalWithUntracked, View,
+    WriteSignal,
 };
 use leptos_router::A;
-use leptos_router::{use_params, Route, Router, Routes};
-use leptos_router::{use_params_map, Params};
+use leptos_router::{
+    use_location, use_navigate, use_params, NavigateOptions, Route, Router, Routes, RoutesProps,
+};
+use leptos_router::{use_params_map, use_query_map, Params};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
+use serde_json::json;
+use web_sys::js_sys::Reflect;
+use web_sys::{HtmlButtonElement, ShareData};
 
 #[component]
 pub fn App() -> impl IntoView {
     provide_meta_context();
+    provide_saved();
+    provide_reading();
+    provide_preferences();
+    provide_subscription();
+    provide_ad_choices();
+    provide_analytics();
     view! {
         <Router>
+            <PageViews />
             <div class="flex flex-col h-full">
-                <Routes>
-                    <Route
-                        path="/"
-                        view=|| {
-                            view! {
-                                <Header />
-                                <PageContainer>
-                                    <ArticlePreviews />
-                                </PageContainer>
-                                <Footer />
-                            }
-                        }
-                    />
-                    <Route
-                        path="/articles/:id"
-                        view=|| {
-                            view! {
-                                <Header small=true />
-                                <PageContainer>
-                                    <Article />
-                                </PageContainer>
-                                <Footer ads=true />
-                            }
-                        }
-                    />
-                    <Route
-                        path="/crosswords/:id"
-                        view=|| {
-                            view! {
-                                <Header />
-                                <PageContainer>
-                                    <Crossword />
-                                </PageContainer>
-                                <Footer />
-                            }
-                        }
-                    />
-                    <Route
-                        path="/*"
-                        view=|| {
-                            view! {
-                                <Header />
-                                <PageContainer>404</PageContainer>
-                                <Footer />
-                            }
-                        }
-                    />
-                </Routes>
+                {Routes(RoutesProps::builder().children(Box::new(routes)).build())}
 
             </div>
         </Router>
     }
 }
 
+fn routes() -> leptos::Fragment {
+    leptos::Fragment::new(
+        ROUTES
+            .iter()
+            .map(|route| {
+                let screen = route.screen;
+                view! { <Route path=route.path view=move || page(screen) /> }.into_view()
+            })
+            .collect(),
+    )
+}
+
+fn page(screen: Screen) -> View {
+    let article = screen == Screen::Article;
+    view! {
+        <Header small=article />
+        <PageContainer>
+            {match screen {
+                Screen::Home => {
+                    view! {
+                        <Title text="The Waratah | Trusted By Dozens" />
+                        <ArticlePreviews />
+                    }
+                        .into_view()
+                }
+                Screen::Article => view! { <Article /> }.into_view(),
+                Screen::Crossword => view! { <Crossword /> }.into_view(),
+                Screen::Archive => view! { <Archive /> }.into_view(),
+                Screen::Classifieds => view! { <Classifieds /> }.into_view(),
+                Screen::Events => view! { <Events /> }.into_view(),
+                Screen::Event => view! { <EventPage /> }.into_view(),
+                Screen::EventMonth => view! { <EventMonth /> }.into_view(),
+                Screen::Horoscopes => view! { <Horoscopes /> }.into_view(),
+                Screen::Saved => view! { <SavedArticles /> }.into_view(),
+                Screen::AdLanding => view! { <AdLanding /> }.into_view(),
+                Screen::AdChoices => view! { <YourAdChoices /> }.into_view(),
+                Screen::Subscribe => view! { <Subscribe /> }.into_view(),
+                Screen::NotFound => view! { <NotFound /> }.into_view(),
+            }}
+        </PageContainer>
+        <Footer ads=article />
+    }
+    .into_view()
+}
+
+fn use_mounted() -> ReadSignal<bool> {
+    let (mounted, set_mounted) = create_signal(false);
+    create_effect(move |_| set_mounted(true));
+    mounted
+}
+
+fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+fn use_today() -> impl Fn() -> Option<NaiveDate> + Copy {
+    let mounted = use_mounted();
+    move || mounted().then(today)
+}
+
 #[component]
 pub fn Header(#[prop(optional)] small: bool) -> impl IntoView {
+    let mounted = use_mounted();
     view! {
-        <header class="relative p-4 text-white bg-black">
+        <header class="relative p-4 text-white bg-black dark:bg-neutral-900">
             <div class="inset-0 items-center justify-between hidden pointer-events-none sm:p-4 sm:absolute sm:flex">
-                <div>{Local::now().format("%B %-d, %Y").to_string()}</div>
-                <A
-                    href="https://angusmason.github.io/theaccountgame"
-                    target="_blank"
-                    class="pointer-events-auto"
-                >
-                    "Sign Up"
-                </A>
+                <div>
+                    {move || mounted().then(|| Local::now().format("%B %-d, %Y").to_string())}
+                </div>
+                <div class="relative flex gap-4 pointer-events-auto">
+                    <HeaderLinks />
+                </div>
             </div>
             <a
                 class="w-full text-center"
@@ -127,10 +175,41 @@ pub fn Header(#[prop(optional)] small: bool) -> impl IntoView {
                         )}
                 </Heading>
             </a>
+            <nav class="relative flex flex-wrap justify-center pt-2 text-sm gap-x-4 gap-y-1 sm:hidden">
+                <HeaderLinks />
+            </nav>
         </header>
     }
 }
 
+#[component]
+pub fn HeaderLinks() -> impl IntoView {
+    let (show_preferences, set_show_preferences) = create_signal(false);
+    let Subscribed(subscription) = expect_context();
+    view! {
+        <button on:click=move |_| set_show_preferences(!show_preferences())>"Preferences"</button>
+        <A href=routes::EVENTS.path>"Events"</A>
+        <A href=routes::HOROSCOPES.path>"Horoscopes"</A>
+        <A href=routes::CLASSIFIEDS.path>"Classifieds"</A>
+        <A href=routes::SAVED.path>"Saved"</A>
+        <A href=routes::SUBSCRIBE.path>
+            {move || {
+                subscription
+                    .with(|subscription| {
+                        subscription
+                            .as_ref()
+                            .and_then(Option::as_ref)
+                            .map_or_else(
+                                || "Subscribe".to_string(),
+                                |subscription| format!("{} subscriber", subscription.tier),
+                            )
+                    })
+            }}
+        </A>
+        {move || show_preferences().then(PreferencesPanel)}
+    }
+}
+
 #[component]
 pub fn PageContainer(children: Children) -> impl IntoView {
     view! {
@@ -153,8 +232,9 @@ pub fn ArticlePreviews() -> impl IntoView {
             content="Australia's most serious newspaper, proudly brought to you by incredible (and a few credible) reporters."
         />
         <div class="flex flex-col items-center w-full gap-2 p-4 md:p-0">
+            {move || filter().is_none().then(ContinueReading)}
             <div
-                class="sticky top-0 z-50 justify-center hidden w-full p-2 transition bg-white md:flex"
+                class="sticky top-0 z-50 justify-center hidden w-full p-2 transition bg-white dark:bg-neutral-950 md:flex"
                 class:shadow=stuck
             >
                 <div class="flex *:px-3 divide-x font-noto justify-center py-2">
@@ -254,9 +334,7 @@ pub fn ArticlePreviews() -> impl IntoView {
                                             ARCHIVE => {
                                                 view! {
                                                     <>
-                                                        <div class="flex flex-col grid-cols-2 gap-2 sm:grid">
-                                                            {all}
-                                                        </div>
+                                                        <ArchivePicker />
                                                     </>
                                                 }
                                             }
@@ -374,20 +452,30 @@ pub fn ArticlePreview(
     article: Article,
     #[prop(optional)] layout: ArticlePreviewLayout,
 ) -> impl IntoView {
+    let Reading { entries, since } = expect_context();
+    let id = article.id;
+    let read = move || {
+        entries.with(|entries| {
+            entries
+                .iter()
+                .any(|entry| entry.id == id && entry.finished())
+        })
+    };
+    let date = article.date;
+    let new = move || since().is_some_and(|since| date > since);
     view! {
-        <A
-            class=format!(
-                "flex gap-3 {}",
+        <div class=move || {
+            format!(
+                "relative flex gap-3 {} {}",
                 match (layout.direction, layout.size) {
                     (ArticleDirection::Horizontal, ArticleSize::Hero) => "flex-row-reverse",
                     (ArticleDirection::Horizontal, ArticleSize::Normal) => "flex-row",
                     (ArticleDirection::Vertical, ArticleSize::Hero) => "flex-col-reverse",
                     (ArticleDirection::Vertical, ArticleSize::Normal) => "flex-col",
                 },
+                if read() { "opacity-60" } else { "" },
             )
-
-            href=format!("/articles/{}", article.id)
-        >
+        }>
             {layout
                 .image
                 .then_some(
@@ -400,20 +488,42 @@ pub fn ArticlePreview(
                     },
                 )}
             <div>
-                {layout
-                    .category
-                    .then_some(
-                        view! {
-                            <div class="font-light text-blue-800">
-                                {article.topic.to_uppercase()}
-                            </div>
-                        },
-                    )} <Heading>
+                <div class="flex justify-between gap-2">
+                    {layout
+                        .category
+                        .then_some(
+                            view! {
+                                <div class="font-light text-blue-800">
+                                    {article.topic.to_uppercase()}
+                                </div>
+                            },
+                        )}
+                    <div class="flex gap-2">
+                        {move || {
+                            new()
+                                .then_some(
+                                    view! {
+                                        <div class="px-1 text-sm text-white bg-blue-800 font-noto">
+                                            "NEW"
+                                        </div>
+                                    },
+                                )
+                        }} <BookmarkButton id=article.id />
+                    </div>
+                </div>
+                <Heading>
                     <article class=if layout.size == ArticleSize::Hero {
                         "text-3xl"
                     } else {
                         "text-xl"
-                    }>{article.title}</article>
+                    }>
+                        <A
+                            href=format!("/articles/{}", article.id)
+                            class="after:absolute after:inset-0"
+                        >
+                            {article.title}
+                        </A>
+                    </article>
                 </Heading>
                 {layout
                     .blurb
@@ -431,90 +541,1769 @@ pub fn ArticlePreview(
                     )}
 
             </div>
-        </A>
+        </div>
+    }
+}
+
+#[component]
+pub fn Article() -> impl IntoView {
+    #[derive(Params, PartialEq)]
+    struct ArticleParams {
+        id: String,
+    }
+    let params = use_params::<ArticleParams>();
+    let article =
+        create_memo(move |_| params.with(|params| find_article(&params.as_ref().ok()?.id)));
+    let navigate = use_navigate();
+    create_effect(move |_| {
+        let Some(article) = article() else {
+            return;
+        };
+        if params.with(|params| params.as_ref().is_ok_and(|params| params.id != article.id)) {
+            navigate(
+                &format!("/articles/{}", article.id),
+                NavigateOptions {
+                    replace: true,
+                    ..NavigateOptions::default()
+                },
+            );
+        }
+    });
+    move || {
+        article().map_or_else(
+            || view! { <NotFound /> }.into_view(),
+            |article| view! { <ArticleContent article=article /> }.into_view(),
+        )
+    }
+}
+
+#[component]
+pub fn ArticleContent(article: &'static Article) -> impl IntoView {
+    let url = article_url(article);
+    let image = absolute(article.image.url);
+    let json_ld = json!({
+        "@context": "https://schema.org",
+        "@type": "NewsArticle",
+        "headline": article.title,
+        "description": article.blurb,
+        "image": [image],
+        "datePublished": article.date.to_string(),
+        "articleSection": article.topic,
+        "wordCount": article.words(),
+        "url": url,
+        "mainEntityOfPage": url,
+        "publisher": {
+            "@type": "NewsMediaOrganization",
+            "name": "The Waratah",
+            "url": env!("SITE"),
+        },
+    });
+    let Reading { entries, .. } = expect_context();
+    let Settings(preferences) = expect_context();
+    let track = use_analytics();
+    let (quote, set_quote) = create_signal(None::<(String, f64, f64)>);
+    let Subscribed(subscription) = expect_context();
+    let (locked, set_locked) = create_signal(false);
+    create_effect(move |_| {
+        let Some(subscribed) = subscription.with(|subscription| {
+            subscription.as_ref().map(|subscription| {
+                subscription
+                    .as_ref()
+                    .is_some_and(|subscription| subscription.tier != Tier::Free)
+            })
+        }) else {
+            return;
+        };
+        let bypassed = METER.bypassed(&document().referrer(), &location().hostname().unwrap());
+        set_locked(
+            !subscribed && !bypassed && !Paywall::new(LocalStorage, METER).open(article, today()),
+        );
+    });
+    let mousedown_handle = window_event_listener(mousedown, move |_| set_quote(None));
+    on_cleanup(move || mousedown_handle.remove());
+    create_effect(move |_| {
+        let history = History::new(LocalStorage);
+        history.open(article.id);
+        entries.set(history.entries());
+    });
+    let (depth, set_depth) = create_signal(0.0);
+    let handle = window_event_listener(scroll, move |_| {
+        set_quote(None);
+        let element = document().scrolling_element().unwrap();
+        let progress = f64::from(element.scroll_top() + element.client_height())
+            / f64::from(element.scroll_height());
+        let progress = (progress * 20.0).floor() / 20.0;
+        let quarter = (progress * 4.0).floor() / 4.0;
+        if quarter > depth.get_untracked() {
+            set_depth(quarter);
+            track(AnalyticsEvent::ReadDepth {
+                article: article.id.to_string(),
+                progress: quarter,
+            });
+        }
+        let recorded = entries.with(|entries| {
+            entries
+                .iter()
+                .find(|entry| entry.id == article.id)
+                .map_or(0.0, |entry| entry.progress)
+        });
+        if progress > recorded {
+            let history = History::new(LocalStorage);
+            history.record_progress(article.id, progress);
+            entries.set(history.entries());
+        }
+    });
+    on_cleanup(move || handle.remove());
+    view! {
+        <Title text=format!("{} | The Waratah", article.title) />
+        <Link rel="canonical" href=url.clone() />
+        <Meta name="description" content=article.blurb />
+        <Meta property="og:site_name" content="The Waratah" />
+        <Meta property="og:type" content="article" />
+        <Meta property="og:title" content=article.title />
+        <Meta property="og:description" content=article.blurb />
+        <Meta property="og:image" content=image.clone() />
+        <Meta property="og:image:alt" content=article.image.caption />
+        <Meta property="og:url" content=url />
+        <Meta property="article:section" content=article.topic />
+        <Meta property="article:published_time" content=article.date.to_string() />
+        <Meta name="twitter:card" content="summary_large_image" />
+        <Meta name="twitter:title" content=article.title />
+        <Meta name="twitter:description" content=article.blurb />
+        <Meta name="twitter:image" content=image />
+        <Meta name="twitter:image:alt" content=article.image.caption />
+        <Script type_="application/ld+json">{json_ld.to_string()}</Script>
+        <div class="w-full max-w-2xl p-4 shrink-0">
+            <div class="flex flex-col gap-4">
+                <div>
+                    <Heading>{article.title.to_uppercase()}</Heading>
+                    <Caption>
+                        <div class="font-serif text-base text-left sm:text-lg">
+                            {article.blurb}
+                        </div>
+                    </Caption>
+                    <div class="flex justify-between gap-2 text-base font-light">
+                        <div class="flex gap-1">
+                            <div class="text-blue-800">{article.topic.to_uppercase()}</div>
+                            "\u{b7} "
+                            {article.reading_time()}
+                            " min read"
+                        </div>
+                        <BookmarkButton id=article.id />
+                    </div>
+                    <ShareTools article=article />
+                </div>
+                <div class="sm:px-16">
+                    <img
+                        src=article.image.url
+                        alt=article.image.caption
+                        class="object-cover w-full aspect-[3/2]"
+                    />
+                    <Caption>{article.image.caption}</Caption>
+                </div>
+                <Divider />
+                <div class="relative">
+                    <div class=move || {
+                        preferences
+                            .with(|preferences| {
+                                format!(
+                                    "{} {} {}",
+                                    "flex flex-col gap-5
+                [&>div:first-child>p]:first-letter:text-[3.45rem]
+                sm:[&>div:first-child>p]:first-letter:text-[3.9rem]
+                [&>div:first-child>p]:first-letter:leading-none
+                [&>div:first-child>p]:first-letter:font-bold
+                [&>div:first-child>p]:first-letter:font-serif
+                [&>div:first-child>p]:first-letter:float-left
+                [&>div:first-child>p]:first-letter:pr-2",
+                                    preferences.font.class(),
+                                    preferences.text_size.class(),
+                                )
+                            })
+                    }
+                        on:mouseup=move |_| set_quote(selected_quote())
+                        on:touchend=move |_| set_quote(selected_quote())
+                    >
+                        {article
+                            .fragments
+                            .iter()
+                            .enumerate()
+                            .scan(0, |paragraphs, (index, fragment)| {
+                                if matches!(fragment, Fragment::Text(_)) {
+                                    *paragraphs += 1;
+                                }
+                                Some((index, fragment, *paragraphs))
+                            })
+                            .map(|(index, fragment, paragraphs)| {
+                                let hidden = move || index > 0 && locked();
+                                let slot = (matches!(fragment, Fragment::Text(_))
+                                    && paragraphs % IN_ARTICLE_EVERY == 0
+                                    && index + 1 < article.fragments.len())
+                                    .then(|| {
+                                        view! {
+                                            <div class="flex justify-center">
+                                                {move || {
+                                                    locked().not().then(|| view! { <AdSlot placement=Slot::InArticle /> })
+                                                }}
+                                            </div>
+                                        }
+                                    });
+                                view! {
+                                    <ArticleFragment fragment=fragment hidden=Signal::derive(hidden) />
+                                    {slot}
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                    {move || locked().then(PaywallPrompt)}
+                </div>
+                {move || {
+                    quote()
+                        .map(|(text, x, y)| {
+                            view! { <QuotePopover article=article text=text x=x y=y set_quote=set_quote /> }
+                        })
+                }}
+                <Divider />
+                <ReadMore this_article=move || article />
+                <Divider />
+                <Comments article=article />
+            </div>
+        </div>
+        <aside class="sticky self-start hidden py-4 top-4 xl:block">
+            <AdSlot placement=Slot::Vertical />
+        </aside>
+    }
+}
+
+#[component]
+pub fn ArticleFragment(
+    fragment: &'static Fragment,
+    #[prop(optional, into)] hidden: MaybeSignal<bool>,
+) -> impl IntoView {
+    let content = match fragment {
+        Fragment::Image(Image { url, caption }) => view! {
+            <img src=*url alt=*caption class="object-cover w-full" />
+            <Caption>{*caption}</Caption>
+        }
+        .into_view(),
+        Fragment::Poll(poll) => view! { <ArticlePoll poll=poll /> }.into_view(),
+        Fragment::Event(id) => EVENTS
+            .iter()
+            .find(|event| event.id == *id)
+            .map(|event| view! { <EventSummary event=event /> })
+            .into_view(),
+        Fragment::Text(text) => view! { <p>{*text}</p> }.into_view(),
+    };
+    view! {
+        <div
+            data-quote=matches!(fragment, Fragment::Text(_))
+            class=("px-16", matches!(fragment, Fragment::Image(_)))
+            class=("blur-sm", hidden)
+            class=("select-none", hidden)
+            aria-hidden=move || hidden().then_some("true")
+        >
+            {content}
+        </div>
+    }
+}
+
+#[component]
+pub fn ArticlePoll(poll: &'static Poll) -> impl IntoView {
+    let (ballot, set_ballot) = create_signal(None::<String>);
+    let (results, set_results) = create_signal(None);
+    create_effect(move |_| {
+        let choice = Ballots::new(LocalStorage).get(poll.id);
+        if choice.is_some() {
+            spawn_local(async move {
+                set_results(Some(LocalPolls::new(LocalStorage).results(poll.id).await));
+            });
+        }
+        set_ballot(choice);
+    });
+    let vote = move |option: &'static str| {
+        if ballot.get_untracked().is_some() {
+            return;
+        }
+        set_ballot(Some(option.to_string()));
+        spawn_local(async move {
+            set_results(Some(
+                LocalPolls::new(LocalStorage).vote(poll.id, option).await,
+            ));
+        });
+    };
+    view! {
+        <div class="flex flex-col gap-3 p-4 text-base border font-noto border-neutral-400">
+            <div class="text-xl font-semibold">{poll.question}</div>
+            {move || match (ballot(), results()) {
+                (None, _) => {
+                    poll.options
+                        .iter()
+                        .map(|option| {
+                            view! {
+                                <button
+                                    class="p-2 text-left border border-neutral-400 hover:border-blue-800"
+                                    on:click=move |_| vote(option)
+                                >
+                                    {*option}
+                                </button>
+                            }
+                        })
+                        .collect_view()
+                }
+                (Some(_), None) => view! { <div class="font-light">"Counting votes..."</div> }.into_view(),
+                (Some(_), Some(Err(error))) => view! { <div class="font-light">{error.to_string()}</div> }.into_view(),
+                (Some(choice), Some(Ok(results))) => {
+                    let count = |option: &str| results.get(option).copied().unwrap_or(0);
+                    let total = poll.options.iter().map(|option| count(option)).sum::<usize>().max(1);
+                    poll.options
+                        .iter()
+                        .map(|option| {
+                            let percent = count(option) * 100 / total;
+                            view! {
+                                <div class="flex flex-col gap-1">
+                                    <div class="flex justify-between gap-2">
+                                        <div class:font-semibold=choice == *option>{*option}</div>
+                                        <div class="font-light">{format!("{percent}%")}</div>
+                                    </div>
+                                    <div class="h-2 bg-gray-200 dark:bg-neutral-700">
+                                        <div class="h-2 bg-blue-800" style=format!("width: {percent}%")></div>
+                                    </div>
+                                </div>
+                            }
+                        })
+                        .collect_view()
+                }
+            }}
+        </div>
+    }
+}
+
+#[component]
+pub fn PaywallPrompt() -> impl IntoView {
+    view! {
+        <div class="absolute inset-x-0 flex justify-center top-24">
+            <div class="flex flex-col items-center max-w-md gap-3 p-6 text-center bg-white border shadow font-noto dark:bg-neutral-900 dark:border-neutral-700">
+                <CategoryHeading>"You've hit the paywall"</CategoryHeading>
+                <div class="font-serif text-lg">
+                    {format!(
+                        "You've read your {} free articles this month. Subscribe to keep reading, or wait for next month like everyone else.",
+                        METER.allowance,
+                    )}
+                </div>
+                <A
+                    href=routes::SUBSCRIBE.path
+                    class="px-4 py-2 text-white bg-black rounded dark:bg-white dark:text-black"
+                >
+                    "Subscribe"
+                </A>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn ArchivePicker(#[prop(optional)] selected: Option<(i32, u32)>) -> impl IntoView {
+    let months = archive(&ARTICLES);
+    let years = months.keys().map(|(year, _)| *year).unique().sorted().rev();
+    view! {
+        <div class="flex flex-col gap-4 py-4 font-noto">
+            {years
+                .map(|year| {
+                    view! {
+                        <div class="flex flex-col gap-2">
+                            <CategoryHeading>{year}</CategoryHeading>
+                            <div class="grid grid-cols-4 gap-2 sm:grid-cols-6 lg:grid-cols-12">
+                                {(1..=12)
+                                    .map(|month| {
+                                        let count = months.get(&(year, month)).map_or(0, Vec::len);
+                                        let name = NaiveDate::from_ymd_opt(year, month, 1)
+                                            .unwrap()
+                                            .format("%b")
+                                            .to_string();
+                                        let class = if selected == Some((year, month)) {
+                                            "flex flex-col items-center p-2 border border-blue-800 text-blue-800"
+                                        } else if count == 0 {
+                                            "flex flex-col items-center p-2 border opacity-30 pointer-events-none"
+                                        } else {
+                                            "flex flex-col items-center p-2 border hover:border-gray-800"
+                                        };
+                                        view! {
+                                            <A class=class href=format!("/archive/{year}/{month:02}")>
+                                                <div>{name}</div>
+                                                <div class="text-xs font-light">
+                                                    {count}
+                                                    {if count == 1 { " story" } else { " stories" }}
+                                                </div>
+                                            </A>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </div>
+                        </div>
+                    }
+                })
+                .collect_view()}
+        </div>
+    }
+}
+
+#[component]
+pub fn Archive() -> impl IntoView {
+    let params = use_params_map();
+    let page = create_memo(move |_| {
+        params.with(|params| {
+            let year = params.get("year")?.parse::<i32>().ok()?;
+            let month = params.get("month")?.parse::<u32>().ok()?;
+            let page = params
+                .get("page")
+                .map_or(Some(1), |page| page.parse().ok())?;
+            NaiveDate::from_ymd_opt(year, month, 1)?;
+            Some((year, month, page))
+        })
+    });
+    move || {
+        let Some((year, month, page)) = page() else {
+            return view! { <NotFound /> }.into_view();
+        };
+        let months = archive(&ARTICLES);
+        let articles = months.get(&(year, month)).cloned().unwrap_or_default();
+        let pages = articles.len().div_ceil(ARCHIVE_PAGE_SIZE).max(1);
+        if page == 0 || page > pages {
+            return view! { <NotFound /> }.into_view();
+        }
+        let name = NaiveDate::from_ymd_opt(year, month, 1)
+            .unwrap()
+            .format("%B %Y")
+            .to_string();
+        let count = articles.len();
+        let href = move |page: usize| {
+            if page == 1 {
+                format!("/archive/{year}/{month:02}")
+            } else {
+                format!("/archive/{year}/{month:02}/{page}")
+            }
+        };
+        view! {
+            <Title text=format!("Archive: {name} | The Waratah") />
+            <div class="flex flex-col w-full max-w-6xl gap-2 p-4">
+                <ArchivePicker selected=(year, month) />
+                <Divider />
+                <CategoryHeading>
+                    {name} " \u{b7} " {count} {if count == 1 { " story" } else { " stories" }}
+                </CategoryHeading>
+                {articles
+                    .is_empty()
+                    .then_some(view! { <div class="font-serif">"No stories this month."</div> })}
+                <div class="flex flex-col grid-cols-2 gap-2 sm:grid">
+                    {articles
+                        .iter()
+                        .skip((page - 1) * ARCHIVE_PAGE_SIZE)
+                        .take(ARCHIVE_PAGE_SIZE)
+                        .map(|article| view! { <ArticlePreview article=(*article).clone() /> })
+                        .collect_view()}
+                </div>
+                {(pages > 1)
+                    .then(|| {
+                        view! {
+                            <div class="flex justify-center gap-3 py-4 font-noto">
+                                {(page > 1)
+                                    .then(|| view! { <A href=href(page - 1)>"\u{2190} Newer"</A> })}
+                                {(1..=pages)
+                                    .map(|number| {
+                                        view! {
+                                            <A
+                                                href=href(number)
+                                                class=(number == page).then_some("font-bold text-blue-800")
+                                            >
+                                                {number}
+                                            </A>
+                                        }
+                                    })
+                                    .collect_view()}
+                                {(page < pages)
+                                    .then(|| view! { <A href=href(page + 1)>"Older \u{2192}"</A> })}
+                            </div>
+                        }
+                    })}
+            </div>
+        }
+        .into_view()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Saved(RwSignal<Vec<String>>);
+
+fn provide_saved() {
+    let saved = create_rw_signal(Vec::new());
+    create_effect(move |_| saved.set(Bookmarks::new(LocalStorage).ids()));
+    provide_context(Saved(saved));
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Reading {
+    entries: RwSignal<Vec<Entry>>,
+    since: RwSignal<Option<NaiveDate>>,
+}
+
+fn provide_reading() {
+    let entries = create_rw_signal(Vec::new());
+    let since = create_rw_signal(None);
+    create_effect(move |_| {
+        let history = History::new(LocalStorage);
+        since.set(history.visit(Local::now().date_naive()));
+        entries.set(history.entries());
+    });
+    provide_context(Reading { entries, since });
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Settings(RwSignal<Preferences>);
+
+fn provide_preferences() {
+    let preferences = create_rw_signal(Preferences::default());
+    create_effect(move |_| preferences.set(Preferences::load(&LocalStorage)));
+    create_effect(move |_| {
+        let dark = match preferences().theme {
+            Theme::Light => false,
+            Theme::Dark => true,
+            Theme::System => window()
+                .match_media("(prefers-color-scheme: dark)")
+                .ok()
+                .flatten()
+                .is_some_and(|query| query.matches()),
+        };
+        let classes = document().document_element().unwrap().class_list();
+        if dark {
+            classes.add_1("dark").unwrap();
+        } else {
+            classes.remove_1("dark").unwrap();
+        }
+    });
+    provide_context(Settings(preferences));
+}
+
+#[component]
+pub fn PreferencesPanel() -> impl IntoView {
+    let Settings(preferences) = expect_context();
+    let update = move |change: &dyn Fn(&mut Preferences)| {
+        preferences.update(change);
+        preferences.with(|preferences| preferences.save(&LocalStorage));
+    };
+    macro_rules! options {
+        ($label:literal, $type:ty, $field:ident) => {
+            view! {
+                <div class="flex flex-col gap-1">
+                    <div class="text-sm opacity-50">{$label}</div>
+                    <div class="flex border divide-x border-neutral-500 divide-neutral-500">
+                        {<$type>::VARIANTS
+                            .iter()
+                            .map(|option| {
+                                view! {
+                                    <button
+                                        class="px-2 py-1 grow"
+                                        class=(
+                                            "bg-neutral-700",
+                                            move || preferences.with(|preferences| preferences.$field == *option),
+                                        )
+                                        on:click=move |_| update(&|preferences| preferences.$field = *option)
+                                    >
+                                        {option.to_string()}
+                                    </button>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                </div>
+            }
+        };
+    }
+    view! {
+        <div class="absolute right-0 z-50 flex flex-col gap-3 p-4 text-white bg-black border top-8 w-72 font-noto border-neutral-700">
+            {options!("Theme", Theme, theme)}
+            {options!("Text size", TextSize, text_size)}
+            {options!("Font", Font, font)}
+        </div>
+    }
+}
+
+#[component]
+pub fn ContinueReading() -> impl IntoView {
+    let Reading { entries, since } = expect_context();
+    let unfinished = move || {
+        entries.with(|entries| {
+            entries
+                .iter()
+                .filter(|entry| !entry.finished())
+                .filter_map(|entry| Some((find_article(&entry.id)?, entry.progress)))
+                .take(3)
+                .collect_vec()
+        })
+    };
+    move || {
+        let unfinished = unfinished();
+        (!unfinished.is_empty()).then(|| {
+            view! {
+                <div class="w-full max-w-6xl px-4 pt-4 shrink-0">
+                    <div class="flex justify-between gap-2">
+                        <CategoryHeading>"Continue reading"</CategoryHeading>
+                        <button
+                            class="font-light text-blue-800 font-noto"
+                            on:click=move |_| {
+                                let history = History::new(LocalStorage);
+                                history.clear();
+                                entries.set(history.entries());
+                                since.set(None);
+                            }
+                        >
+                            "Clear history"
+                        </button>
+                    </div>
+                    <div class="grid gap-4 py-2 md:grid-cols-3">
+                        {unfinished
+                            .into_iter()
+                            .map(|(article, progress)| {
+                                view! {
+                                    <div class="flex flex-col gap-1">
+                                        <ArticlePreview
+                                            article=article.clone()
+                                            layout=ArticlePreviewLayout::default()
+                                                .without_image()
+                                                .without_blurb()
+                                        />
+                                        <div class="h-1 bg-gray-200">
+                                            <div
+                                                class="h-1 bg-blue-800"
+                                                style=format!("width: {}%", (progress * 100.0).round())
+                                            ></div>
+                                        </div>
+                                    </div>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                </div>
+            }
+        })
+    }
+}
+
+#[component]
+pub fn BookmarkButton(id: &'static str) -> impl IntoView {
+    let Saved(saved) = expect_context();
+    let is_saved = move || saved.with(|ids| ids.iter().any(|saved| saved == id));
+    view! {
+        <button
+            class="relative z-10 font-light text-blue-800 shrink-0 font-noto"
+            title=move || if is_saved() { "Remove from saved" } else { "Save for later" }
+            on:click=move |_| {
+                let bookmarks = Bookmarks::new(LocalStorage);
+                bookmarks.toggle(id);
+                saved.set(bookmarks.ids());
+            }
+        >
+            {move || if is_saved() { "\u{2605} Saved" } else { "\u{2606} Save" }}
+        </button>
+    }
+}
+
+#[component]
+pub fn SavedArticles() -> impl IntoView {
+    let Saved(saved) = expect_context();
+    view! {
+        <Title text="Saved | The Waratah" />
+        <div class="w-full max-w-2xl p-4 shrink-0">
+            <div class="flex flex-col gap-4">
+                <Heading>"Saved"</Heading>
+                <Divider />
+                <div class="flex flex-col w-full gap-4 [&_img]:w-1/4">
+                    {move || {
+                        let articles = saved
+                            .get()
+                            .iter()
+                            .filter_map(|id| find_article(id))
+                            .collect_vec();
+                        if articles.is_empty() {
+                            return view! {
+                                <div class="font-serif text-lg">
+                                    "Stories you save will show up here."
+                                </div>
+                            }
+                                .into_view();
+                        }
+                        articles
+                            .into_iter()
+                            .map(|article| {
+                                view! {
+                                    <div class="flex items-start gap-4">
+                                        <div class="grow">
+                                            <ArticlePreview
+                                                article=article.clone()
+                                                layout=ArticlePreviewLayout::default()
+                                                    .without_blurb()
+                                                    .horizontal()
+                                            />
+                                        </div>
+                                        <button
+                                            class="text-sm font-light text-blue-800 font-noto"
+                                            on:click=move |_| {
+                                                let bookmarks = Bookmarks::new(LocalStorage);
+                                                bookmarks.remove(article.id);
+                                                saved.set(bookmarks.ids());
+                                            }
+                                        >
+                                            "Remove"
+                                        </button>
+                                    </div>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn Classifieds() -> impl IntoView {
+    let query = use_query_map();
+    let selected = move || query.with(|query| query.get("category").cloned());
+    let today = use_today();
+    let categories = CLASSIFIEDS
+        .iter()
+        .map(|classified| classified.category)
+        .unique()
+        .sorted_unstable()
+        .collect_vec();
+    view! {
+        <Title text="Classifieds | The Waratah" />
+        <Meta
+            name="description"
+            content="Buy, sell and find lost things in Warrah. Listings disappear when they expire."
+        />
+        <div class="w-full max-w-4xl p-4 shrink-0">
+            <div class="flex flex-col gap-4">
+                <Heading>"Classifieds"</Heading>
+                <div class="flex flex-wrap gap-4 font-noto">
+                    {once(None)
+                        .chain(categories.into_iter().map(Some))
+                        .map(|category| {
+                            let href = category
+                                .map_or_else(
+                                    || routes::CLASSIFIEDS.path.to_string(),
+                                    |category| {
+                                        format!("{}?category={}", routes::CLASSIFIEDS.path, encode(category))
+                                    },
+                                );
+                            view! {
+                                <A
+                                    href=href
+                                    class=move || {
+                                        if selected().as_deref() == category { "text-blue-800" } else { "" }
+                                    }
+                                >
+                                    {category.unwrap_or("All")}
+                                </A>
+                            }
+                        })
+                        .collect_view()}
+                </div>
+                <Divider />
+                {move || {
+                    let Some(today) = today() else {
+                        return ().into_view();
+                    };
+                    let listings = CLASSIFIEDS
+                        .iter()
+                        .filter(|classified| {
+                            selected().map_or(true, |selected| classified.category == selected)
+                                && !classified.expired(today)
+                        })
+                        .collect_vec();
+                    if listings.is_empty() {
+                        return view! {
+                            <div class="font-serif text-lg">"Nothing listed here right now."</div>
+                        }
+                            .into_view();
+                    }
+                    view! {
+                        <div class="grid gap-4 md:grid-cols-2">
+                            {listings
+                                .into_iter()
+                                .map(|classified| {
+                                    view! {
+                                        <div class="flex flex-col gap-2 p-4 border border-neutral-400">
+                                            <div class="flex justify-between gap-2 font-light font-noto">
+                                                <div class="text-blue-800">
+                                                    {classified.category.to_uppercase()}
+                                                </div>
+                                                <div class="font-semibold">
+                                                    {classified.price.map(|price| price.to_string())}
+                                                </div>
+                                            </div>
+                                            <div class="text-2xl font-semibold font-noto">
+                                                {classified.title}
+                                            </div>
+                                            <div class="font-serif text-lg">{classified.body}</div>
+                                            <div class="font-noto">{classified.contact}</div>
+                                            <div class="text-sm font-light font-noto opacity-50">
+                                                {format!(
+                                                    "Listed {} \u{b7} Expires {}",
+                                                    classified.posted.format("%B %-d, %Y"),
+                                                    classified.expires.format("%B %-d, %Y"),
+                                                )}
+                                            </div>
+                                        </div>
+                                    }
+                                })
+                                .collect_view()}
+                        </div>
+                    }
+                        .into_view()
+                }}
+            </div>
+        </div>
+    }
+}
+
+fn event_time(event: &Event) -> String {
+    let start = event.start.format("%A %B %-d, %Y, %-I:%M %P");
+    if event.start.date() == event.end.date() {
+        format!("{start} to {}", event.end.format("%-I:%M %P"))
+    } else {
+        format!(
+            "{start} to {}",
+            event.end.format("%A %B %-d, %Y, %-I:%M %P")
+        )
+    }
+}
+
+#[component]
+pub fn EventSummary(event: &'static Event) -> impl IntoView {
+    view! {
+        <A
+            href=format!("/events/{}", event.id)
+            class="flex flex-col gap-1 p-4 text-base border font-noto border-neutral-400 hover:border-blue-800"
+        >
+            <div class="font-light text-blue-800">{event.venue.to_uppercase()}</div>
+            <div class="text-xl font-semibold">{event.title}</div>
+            <div class="font-light">{event_time(event)}</div>
+        </A>
+    }
+}
+
+#[component]
+pub fn EventViews(#[prop(optional)] month: Option<(i32, u32)>) -> impl IntoView {
+    let today = use_today();
+    let month_href = move || {
+        let months = event_months(&EVENTS);
+        let today = today().map(|today| (today.year(), today.month()));
+        month
+            .or_else(|| {
+                today.and_then(|today| months.iter().copied().find(|month| *month >= today))
+            })
+            .or_else(|| months.last().copied())
+            .or(today)
+            .map_or_else(
+                || routes::EVENTS.path.to_string(),
+                |(year, month)| format!("/events/{year}/{month:02}"),
+            )
+    };
+    view! {
+        <div class="flex flex-wrap gap-4 font-noto">
+            <A href=routes::EVENTS.path class=if month.is_none() { "text-blue-800" } else { "" }>
+                "Agenda"
+            </A>
+            <A href=month_href class=if month.is_some() { "text-blue-800" } else { "" }>
+                "Month"
+            </A>
+            <a href="/events.ics" download="events.ics">
+                "Subscribe to the calendar"
+            </a>
+        </div>
+    }
+}
+
+#[component]
+pub fn Events() -> impl IntoView {
+    let today = use_today();
+    view! {
+        <Title text="Events | The Waratah" />
+        <Meta name="description" content="What's on in Warrah: community events, shows and sausage sizzles." />
+        <div class="w-full max-w-4xl p-4 shrink-0">
+            <div class="flex flex-col gap-4">
+                <Heading>"Events"</Heading>
+                <EventViews />
+                <Divider />
+                {move || {
+                    let Some(today) = today() else {
+                        return ().into_view();
+                    };
+                    let upcoming = EVENTS
+                        .iter()
+                        .filter(|event| event.end.date() >= today)
+                        .collect_vec();
+                    if upcoming.is_empty() {
+                        return view! {
+                            <div class="font-serif text-lg">"Nothing's on. Check back soon."</div>
+                        }
+                            .into_view();
+                    }
+                    upcoming
+                        .into_iter()
+                        .chunk_by(|event| event.start.date())
+                        .into_iter()
+                        .map(|(date, events)| {
+                            view! {
+                                <div class="flex flex-col gap-2">
+                                    <CategoryHeading>{date.format("%A %B %-d, %Y").to_string()}</CategoryHeading>
+                                    {events.map(|event| view! { <EventSummary event=event /> }).collect_view()}
+                                </div>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn EventMonth() -> impl IntoView {
+    let params = use_params_map();
+    let month = create_memo(move |_| {
+        params.with(|params| {
+            let year = params.get("year")?.parse::<i32>().ok()?;
+            let month = params.get("month")?.parse::<u32>().ok()?;
+            NaiveDate::from_ymd_opt(year, month, 1).map(|first| (year, month, first))
+        })
+    });
+    move || {
+        let Some((year, month, first)) = month() else {
+            return view! { <NotFound /> }.into_view();
+        };
+        let months = event_months(&EVENTS);
+        let previous = months
+            .iter()
+            .rev()
+            .find(|other| **other < (year, month))
+            .copied();
+        let next = months.iter().find(|other| **other > (year, month)).copied();
+        let start = first - Days::new(u64::from(first.weekday().num_days_from_monday()));
+        let weeks = start
+            .iter_days()
+            .take_while(|day| {
+                *day < first || day.month() == month || day.weekday().num_days_from_monday() != 0
+            })
+            .collect_vec();
+        let name = first.format("%B %Y").to_string();
+        let link = |month: Option<(i32, u32)>, label: &'static str| {
+            month.map(|(year, month)| {
+                view! { <A href=format!("/events/{year}/{month:02}")>{label}</A> }
+            })
+        };
+        view! {
+            <Title text=format!("Events: {name} | The Waratah") />
+            <div class="w-full max-w-6xl p-4 shrink-0">
+                <div class="flex flex-col gap-4">
+                    <Heading>"Events"</Heading>
+                    <EventViews month=(year, month) />
+                    <Divider />
+                    <div class="flex items-center justify-between gap-4 font-noto">
+                        <div>{link(previous, "\u{2190} Earlier")}</div>
+                        <CategoryHeading>{name}</CategoryHeading>
+                        <div>{link(next, "Later \u{2192}")}</div>
+                    </div>
+                    <div class="grid grid-cols-7 text-sm border-t border-l font-noto border-neutral-400">
+                        {["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]
+                            .into_iter()
+                            .map(|day| {
+                                view! { <div class="p-1 font-semibold border-b border-r border-neutral-400">{day}</div> }
+                            })
+                            .collect_view()}
+                        {weeks
+                            .into_iter()
+                            .map(|day| {
+                                view! {
+                                    <div
+                                        class="flex flex-col gap-1 p-1 border-b border-r min-h-24 border-neutral-400"
+                                        class=("opacity-40", day.month() != month)
+                                    >
+                                        <div class="font-light">{day.day()}</div>
+                                        {EVENTS
+                                            .iter()
+                                            .filter(|event| event.start.date() <= day && day <= event.end.date())
+                                            .map(|event| {
+                                                view! {
+                                                    <A
+                                                        href=format!("/events/{}", event.id)
+                                                        class="px-1 text-white bg-blue-800 truncate"
+                                                    >
+                                                        {event.title}
+                                                    </A>
+                                                }
+                                            })
+                                            .collect_view()}
+                                    </div>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                </div>
+            </div>
+        }
+            .into_view()
+    }
+}
+
+#[component]
+pub fn EventPage() -> impl IntoView {
+    let params = use_params_map();
+    let event = create_memo(move |_| {
+        params.with(|params| {
+            let id = params.get("id")?;
+            EVENTS.iter().find(|event| event.id == id)
+        })
+    });
+    move || {
+        let Some(event) = event() else {
+            return view! { <NotFound /> }.into_view();
+        };
+        let url = format!("{}/events/{}", env!("SITE"), event.id);
+        let json_ld = json!({
+            "@context": "https://schema.org",
+            "@type": "Event",
+            "name": event.title,
+            "description": event.description,
+            "startDate": event.start.format("%Y-%m-%dT%H:%M:00+08:00").to_string(),
+            "endDate": event.end.format("%Y-%m-%dT%H:%M:00+08:00").to_string(),
+            "location": {
+                "@type": "Place",
+                "name": event.venue,
+                "address": "Warrah, WA",
+            },
+            "url": url,
+        });
+        view! {
+            <Title text=format!("{} | The Waratah", event.title) />
+            <Link rel="canonical" href=url />
+            <Meta name="description" content=event.description />
+            <Script type_="application/ld+json">{json_ld.to_string()}</Script>
+            <div class="w-full max-w-2xl p-4 shrink-0">
+                <div class="flex flex-col gap-4">
+                    <EventViews />
+                    <div>
+                        <Heading>{event.title.to_uppercase()}</Heading>
+                        <div class="flex flex-col gap-1 text-base font-light font-noto">
+                            <div class="text-blue-800">{event.venue.to_uppercase()}</div>
+                            <div>{event_time(event)}</div>
+                        </div>
+                    </div>
+                    <Divider />
+                    <div class="font-serif text-lg/[1.75rem] sm:text-xl/[2rem]">{event.description}</div>
+                    <div class="font-noto">
+                        <a
+                            class="text-blue-800"
+                            href=format!("/events/{}.ics", event.id)
+                            download=format!("{}.ics", event.id)
+                        >
+                            "Add to calendar"
+                        </a>
+                    </div>
+                </div>
+            </div>
+        }
+            .into_view()
+    }
+}
+
+#[component]
+pub fn Horoscopes() -> impl IntoView {
+    let params = use_params_map();
+    let today = use_today();
+    let requested = create_memo(move |_| {
+        params.with(|params| {
+            params
+                .get("date")
+                .map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        })
+    });
+    let description = "What the stars have in store for Warrah, updated daily and about as accurate as the weather.";
+    move || {
+        let Some(today) = today() else {
+            return view! {
+                <Title text="Horoscopes | The Waratah" />
+                <Meta name="description" content=description />
+                <div class="w-full max-w-6xl p-4 shrink-0">
+                    <Heading>"Horoscopes"</Heading>
+                </div>
+            }
+            .into_view();
+        };
+        let date = match requested() {
+            None => today,
+            Some(Some(date)) if date <= today => date,
+            _ => return view! { <NotFound /> }.into_view(),
+        };
+        let href = |date: NaiveDate| {
+            if date == today {
+                routes::HOROSCOPES.path.to_string()
+            } else {
+                format!("{}/{}", routes::HOROSCOPES.path, date.format("%Y-%m-%d"))
+            }
+        };
+        let season = Sign::of(date);
+        let name = date.format("%B %-d, %Y").to_string();
+        view! {
+            <Title text=format!("Horoscopes for {name} | The Waratah") />
+            <Meta name="description" content=description />
+            <div class="w-full max-w-6xl p-4 shrink-0">
+                <div class="flex flex-col gap-4">
+                    <Heading>"Horoscopes"</Heading>
+                    <div class="flex items-center justify-between gap-4 font-noto">
+                        <A href=href(date - Days::new(1))>"\u{2190} Previous day"</A>
+                        <div class="text-center">
+                            <div class="text-xl">{name}</div>
+                            <div class="font-light">
+                                {format!("The sun is in {season} {}", season.symbol())}
+                            </div>
+                        </div>
+                        <div>
+                            {(date < today)
+                                .then(|| view! { <A href=href(date + Days::new(1))>"Next day \u{2192}"</A> })}
+                        </div>
+                    </div>
+                    <Divider />
+                    <div class="grid gap-4 md:grid-cols-2 lg:grid-cols-3">
+                        {Sign::VARIANTS
+                            .iter()
+                            .map(|sign| {
+                                let horoscope = horoscope(*sign, date);
+                                view! {
+                                    <div
+                                        class="flex flex-col gap-2 p-4 border border-neutral-400"
+                                        class=("border-blue-800", *sign == season)
+                                    >
+                                        <div class="flex items-baseline justify-between gap-2 font-noto">
+                                            <div class="text-2xl font-semibold">
+                                                {format!("{} {sign}", sign.symbol())}
+                                            </div>
+                                            <div class="text-sm font-light">{sign.dates()}</div>
+                                        </div>
+                                        <div class="font-serif text-lg">{horoscope.reading}</div>
+                                        <div class="text-sm font-light font-noto">
+                                            {format!(
+                                                "Lucky number {} \u{b7} Lucky colour {}",
+                                                horoscope.lucky_number,
+                                                horoscope.lucky_colour,
+                                            )}
+                                        </div>
+                                    </div>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                </div>
+            </div>
+        }
+            .into_view()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Subscribed(RwSignal<Option<Option<Subscription>>>);
+
+fn provide_subscription() {
+    let subscription = create_rw_signal(None);
+    create_effect(move |_| {
+        spawn_local(async move {
+            subscription.set(Some(LocalSubscriptions::new(LocalStorage).current().await));
+        });
+    });
+    provide_context(Subscribed(subscription));
+}
+
+#[component]
+#[allow(clippy::too_many_lines)]
+pub fn Subscribe() -> impl IntoView {
+    let Subscribed(subscription) = expect_context();
+    let sign_up = create_rw_signal(SignUp::default());
+    let (errors, set_errors) = create_signal(Vec::<(Field, &'static str)>::new());
+    let (pending, set_pending) = create_signal(None::<Pending>);
+    let (failure, set_failure) = create_signal(None::<String>);
+    let error = move |field| {
+        move || {
+            errors.with(|errors| {
+                errors.iter().find(|(invalid, _)| *invalid == field).map(
+                    |(_, message)| view! { <div class="text-sm text-red-700">{*message}</div> },
+                )
+            })
+        }
+    };
+    let submit = move |event: SubmitEvent| {
+        event.prevent_default();
+        let errors = sign_up.with_untracked(SignUp::validate);
+        set_errors(errors.clone());
+        if !errors.is_empty() {
+            return;
+        }
+        spawn_local(async move {
+            match LocalSubscriptions::new(LocalStorage)
+                .subscribe(sign_up.get_untracked())
+                .await
+            {
+                Ok(pending) => {
+                    set_failure(None);
+                    set_pending(Some(pending));
+                }
+                Err(error) => set_failure(Some(error.to_string())),
+            }
+        });
+    };
+    let confirm = move |token: String| {
+        spawn_local(async move {
+            match LocalSubscriptions::new(LocalStorage).confirm(&token).await {
+                Ok(confirmed) => {
+                    set_pending(None);
+                    subscription.set(Some(Some(confirmed)));
+                }
+                Err(error) => {
+                    set_pending(None);
+                    set_failure(Some(error.to_string()));
+                }
+            }
+        });
+    };
+    let cancel = move |_| {
+        spawn_local(async move {
+            if LocalSubscriptions::new(LocalStorage).cancel().await.is_ok() {
+                subscription.set(Some(None));
+                sign_up.set(SignUp::default());
+            }
+        });
+    };
+    view! {
+        <Title text="Subscribe | The Waratah" />
+        <Meta
+            name="description"
+            content="Subscribe to The Waratah for unlimited articles, every crossword and a printed edition delivered by seagull."
+        />
+        <div class="w-full max-w-4xl p-4 shrink-0">
+            <div class="flex flex-col gap-4 font-noto">
+                <Heading>"Subscribe"</Heading>
+                <Divider />
+                {move || {
+                    if let Some(current) = subscription.get().flatten() {
+                        return view! {
+                            <div class="flex flex-col gap-2">
+                                <div class="font-serif text-lg">
+                                    {format!(
+                                        "Thanks, {}. You've been a {} subscriber since {}.",
+                                        current.name,
+                                        current.tier,
+                                        current.since.with_timezone(&Local).format("%B %-d, %Y"),
+                                    )}
+                                </div>
+                                <div>
+                                    <button class="font-light text-blue-800" on:click=cancel>
+                                        "Cancel subscription"
+                                    </button>
+                                </div>
+                            </div>
+                        }
+                            .into_view();
+                    }
+                    if let Some(pending) = pending.get() {
+                        let token = pending.token.clone();
+                        return view! {
+                            <div class="flex flex-col gap-3">
+                                <CategoryHeading>"Confirm your subscription"</CategoryHeading>
+                                <div class="font-serif text-lg">
+                                    {format!(
+                                        "{} ({}), on the {} plan at {}.",
+                                        pending.sign_up.name,
+                                        pending.sign_up.email,
+                                        pending.sign_up.tier,
+                                        pending.sign_up.tier.price(),
+                                    )}
+                                </div>
+                                <div class="flex gap-4">
+                                    <button
+                                        class="px-4 py-2 text-white bg-black rounded dark:bg-white dark:text-black"
+                                        on:click=move |_| confirm(token.clone())
+                                    >
+                                        "Confirm"
+                                    </button>
+                                    <button
+                                        class="font-light text-blue-800"
+                                        on:click=move |_| set_pending(None)
+                                    >
+                                        "Back"
+                                    </button>
+                                </div>
+                            </div>
+                        }
+                            .into_view();
+                    }
+                    view! {
+                        <form class="flex flex-col gap-4" on:submit=submit>
+                            <div class="grid gap-4 md:grid-cols-3">
+                                {Tier::VARIANTS
+                                    .iter()
+                                    .map(|tier| {
+                                        let tier = *tier;
+                                        view! {
+                                            <button
+                                                type="button"
+                                                class="flex flex-col gap-2 p-4 text-left border"
+                                                class=(
+                                                    "border-blue-800",
+                                                    move || sign_up.with(|sign_up| sign_up.tier == tier),
+                                                )
+                                                on:click=move |_| sign_up.update(|sign_up| sign_up.tier = tier)
+                                            >
+                                                <div class="text-xl font-semibold">{tier.to_string()}</div>
+                                                <div class="text-blue-800">{tier.price()}</div>
+                                                <ul class="text-sm font-light list-disc list-inside">
+                                                    {tier
+                                                        .perks()
+                                                        .iter()
+                                                        .map(|perk| view! { <li>{*perk}</li> })
+                                                        .collect_view()}
+                                                </ul>
+                                            </button>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </div>
+                            <label class="flex flex-col gap-1">
+                                "Name"
+                                <input
+                                    class="p-2 bg-transparent border border-neutral-400"
+                                    autocomplete="name"
+                                    prop:value=move || sign_up.with(|sign_up| sign_up.name.clone())
+                                    on:input=move |event| {
+                                        sign_up.update(|sign_up| sign_up.name = event_target_value(&event));
+                                    }
+                                />
+                                {error(Field::Name)}
+                            </label>
+                            <label class="flex flex-col gap-1">
+                                "Email"
+                                <input
+                                    class="p-2 bg-transparent border border-neutral-400"
+                                    type="email"
+                                    autocomplete="email"
+                                    prop:value=move || sign_up.with(|sign_up| sign_up.email.clone())
+                                    on:input=move |event| {
+                                        sign_up.update(|sign_up| sign_up.email = event_target_value(&event));
+                                    }
+                                />
+                                {error(Field::Email)}
+                            </label>
+                            <label class="flex flex-col gap-1">
+                                <div class="flex gap-2">
+                                    <input
+                                        type="checkbox"
+                                        prop:checked=move || sign_up.with(|sign_up| sign_up.accepted_terms)
+                                        on:change=move |event| {
+                                            sign_up
+                                                .update(|sign_up| {
+                                                    sign_up.accepted_terms = event_target_checked(&event);
+                                                });
+                                        }
+                                    />
+                                    "I agree to be trusted by dozens."
+                                </div>
+                                {error(Field::Terms)}
+                            </label>
+                            {move || failure().map(|failure| view! { <div class="text-red-700">{failure}</div> })}
+                            <div>
+                                <button class="px-4 py-2 text-white bg-black rounded dark:bg-white dark:text-black">
+                                    "Continue"
+                                </button>
+                            </div>
+                        </form>
+                    }
+                        .into_view()
+                }}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn NotFound() -> impl IntoView {
+    let location = use_location();
+    let suggestions = move || {
+        location.pathname.with(|path| {
+            let requested = path
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .unwrap_or_default();
+            ARTICLES
+                .iter()
+                .map(|article| (edit_distance(requested, article.id), article))
+                .filter(|(distance, article)| *distance * 3 <= article.id.len())
+                .sorted_by_key(|(distance, _)| *distance)
+                .map(|(_, article)| article)
+                .take(3)
+                .collect_vec()
+        })
+    };
+    view! {
+        <Title text="Page Not Found | The Waratah" />
+        <Meta name="robots" content="noindex" />
+        <div class="w-full max-w-2xl p-4 shrink-0">
+            <div class="flex flex-col gap-4">
+                <div>
+                    <Heading>"Page Not Found"</Heading>
+                    <Caption>
+                        <div class="font-serif text-base text-left sm:text-lg">
+                            "Our reporters searched everywhere, but this page seems to have gone missing."
+                        </div>
+                    </Caption>
+                </div>
+                {move || {
+                    let suggestions = suggestions();
+                    (!suggestions.is_empty())
+                        .then(|| {
+                            view! {
+                                <div class="flex flex-col gap-4">
+                                    <CategoryHeading>"Did you mean"</CategoryHeading>
+                                    {suggestions
+                                        .into_iter()
+                                        .map(|article| {
+                                            view! {
+                                                <ArticlePreview
+                                                    article=article.clone()
+                                                    layout=ArticlePreviewLayout::default()
+                                                        .without_image()
+                                                />
+                                            }
+                                        })
+                                        .collect_view()}
+                                </div>
+                            }
+                        })
+                }}
+                <Divider />
+                <div class="flex flex-col w-full gap-4 [&_img]:w-1/4">
+                    <Heading>"Latest"</Heading>
+                    {ARTICLES
+                        .iter()
+                        .take(3)
+                        .map(|article| {
+                            view! {
+                                <ArticlePreview
+                                    article=article.clone()
+                                    layout=ArticlePreviewLayout::default().without_blurb().horizontal()
+                                />
+                            }
+                        })
+                        .collect_view()}
+                </div>
+            </div>
+        </div>
+    }
+}
+
+fn article_url(article: &Article) -> String {
+    format!("{}/articles/{}", env!("SITE"), article.id)
+}
+
+fn can_share() -> bool {
+    Reflect::has(&window().navigator(), &"share".into()).unwrap_or(false)
+}
+
+fn share(title: &str, text: Option<&str>, url: &str) {
+    let mut data = ShareData::new();
+    data.title(title).url(url);
+    if let Some(text) = text {
+        data.text(text);
+    }
+    let _ = window().navigator().share_with_data(&data);
+}
+
+fn copy(text: &str) {
+    if let Some(clipboard) = window().navigator().clipboard() {
+        let _ = clipboard.write_text(text);
+    }
+}
+
+fn selected_quote() -> Option<(String, f64, f64)> {
+    let selection = window().get_selection().ok()??;
+    let quotable = |node: Option<web_sys::Node>| {
+        node.and_then(|node| node.parent_element())
+            .and_then(|element| element.closest("[data-quote]").ok().flatten())
+            .is_some()
+    };
+    if !quotable(selection.anchor_node()) || !quotable(selection.focus_node()) {
+        return None;
+    }
+    let text = String::from(selection.to_string()).trim().to_string();
+    if text.is_empty() {
+        return None;
+    }
+    let rect = selection.get_range_at(0).ok()?.get_bounding_client_rect();
+    Some((text, rect.left() + rect.width() / 2.0, rect.top()))
+}
+
+#[component]
+pub fn ShareTools(article: &'static Article) -> impl IntoView {
+    let mounted = use_mounted();
+    let url = article_url(article);
+    let (copied, set_copied) = create_signal(false);
+    view! {
+        <div class="flex flex-wrap gap-3 py-2 text-sm font-light text-blue-800 font-noto">
+            {
+                let url = url.clone();
+                move || {
+                    let url = url.clone();
+                    (mounted() && can_share())
+                        .then(|| {
+                            view! {
+                                <button on:click=move |_| share(article.title, None, &url)>
+                                    "Share"
+                                </button>
+                            }
+                        })
+                }
+            }
+            <button on:click={
+                let url = url.clone();
+                move |_| {
+                    copy(&url);
+                    set_copied(true);
+                }
+            }>{move || if copied() { "Link copied" } else { "Copy link" }}</button>
+            {intents(&url, article.title)
+                .map(|(intent, href)| {
+                    view! {
+                        <a href=href target="_blank" rel="noopener noreferrer">
+                            {intent.to_string()}
+                        </a>
+                    }
+                })
+                .collect_view()}
+        </div>
+    }
+}
+
+#[component]
+pub fn QuotePopover(
+    article: &'static Article,
+    text: String,
+    x: f64,
+    y: f64,
+    set_quote: WriteSignal<Option<(String, f64, f64)>>,
+) -> impl IntoView {
+    let url = quote_url(&article_url(article), &text);
+    let quoted = format!("\u{201c}{text}\u{201d}");
+    view! {
+        <div
+            class="fixed z-50 flex gap-3 px-3 py-2 text-sm text-white -translate-x-1/2 -translate-y-full bg-black rounded shadow font-noto dark:bg-neutral-800"
+            style=format!("left: {x}px; top: {}px;", y - 8.0)
+            on:mousedown=|event: MouseEvent| {
+                event.prevent_default();
+                event.stop_propagation();
+            }
+        >
+            {can_share()
+                .then(|| {
+                    let (quoted, url) = (quoted.clone(), url.clone());
+                    view! {
+                        <button on:click=move |_| {
+                            share(article.title, Some(&quoted), &url);
+                            set_quote(None);
+                        }>"Share quote"</button>
+                    }
+                })}
+            <button on:click={
+                let (quoted, url) = (quoted.clone(), url.clone());
+                move |_| {
+                    copy(&format!("{quoted}\n{url}"));
+                    set_quote(None);
+                }
+            }>"Copy quote"</button>
+            {intents(&url, &quoted)
+                .filter(|(intent, _)| *intent != Intent::Email)
+                .map(|(intent, href)| {
+                    view! {
+                        <a href=href target="_blank" rel="noopener noreferrer">
+                            {intent.to_string()}
+                        </a>
+                    }
+                })
+                .collect_view()}
+        </div>
     }
 }
 
 #[component]
-pub fn Article() -> impl IntoView {
-    #[derive(Params, PartialEq)]
-    struct ArticleParams {
-        id: String,
-    }
-    let article = || {
-        use_params::<ArticleParams>().with(|params| {
-            ARTICLES
-                .iter()
-                .find(|article| article.id == params.as_ref().unwrap().id.clone())
-                .unwrap()
-        })
+pub fn Comments(article: &'static Article) -> impl IntoView {
+    let (version, set_version) = create_signal(0);
+    let comments = create_local_resource(version, move |_| async move {
+        HttpComments::default().list(article.id).await
+    });
+    let (reply_to, set_reply_to) = create_signal(None::<u64>);
+    let (reported, set_reported) = create_signal(Vec::<u64>::new());
+    let (author, set_author) = create_signal(String::new());
+    let (body, set_body) = create_signal(String::new());
+    let (error, set_error) = create_signal(None::<CommentError>);
+    let (posting, set_posting) = create_signal(false);
+    let submit = move |event: SubmitEvent| {
+        event.prevent_default();
+        let comment = NewComment {
+            parent: reply_to.get_untracked(),
+            author: author.get_untracked(),
+            body: body.get_untracked(),
+        };
+        set_posting(true);
+        spawn_local(async move {
+            match HttpComments::default().post(article.id, comment).await {
+                Ok(_) => {
+                    set_body(String::new());
+                    set_reply_to(None);
+                    set_error(None);
+                    set_version.update(|version| *version += 1);
+                }
+                Err(error) => set_error(Some(error)),
+            }
+            set_posting(false);
+        });
     };
+    let on_reply = Callback::new(move |id| set_reply_to(Some(id)));
+    let on_flag = Callback::new(move |id| {
+        set_reported.update(|reported| reported.push(id));
+        spawn_local(async move {
+            match HttpComments::default().flag(id).await {
+                Ok(()) => set_version.update(|version| *version += 1),
+                Err(error) => set_error(Some(error)),
+            }
+        });
+    });
     view! {
-        <Meta name="description" content=move || article().blurb />
-        <div class="w-full max-w-2xl p-4 shrink-0">
-            <div class="flex flex-col gap-4">
+        <div class="flex flex-col gap-4 font-noto">
+            <CategoryHeading>"Comments"</CategoryHeading>
+            <form class="flex flex-col gap-2" on:submit=submit>
+                {move || {
+                    reply_to()
+                        .map(|id| {
+                            view! {
+                                <div class="flex gap-2 text-sm font-light">
+                                    {format!("Replying to comment #{id}")}
+                                    <button
+                                        type="button"
+                                        class="text-blue-800"
+                                        on:click=move |_| set_reply_to(None)
+                                    >
+                                        "Cancel"
+                                    </button>
+                                </div>
+                            }
+                        })
+                }}
+                <input
+                    class="p-2 bg-transparent border border-neutral-400"
+                    placeholder="Name"
+                    prop:value=author
+                    on:input=move |event| set_author(event_target_value(&event))
+                />
+                <textarea
+                    class="p-2 bg-transparent border border-neutral-400"
+                    placeholder="Have your say"
+                    rows="4"
+                    prop:value=body
+                    on:input=move |event| set_body(event_target_value(&event))
+                ></textarea>
+                {move || {
+                    error().map(|error| view! { <div class="text-sm text-red-700">{error.to_string()}</div> })
+                }}
                 <div>
-                    <Heading>{move || article().title.to_uppercase()}</Heading>
-                    <Caption>
-                        <div class="font-serif text-base text-left sm:text-lg">
-                            {move || article().blurb}
-                        </div>
-                    </Caption>
-                    <div class="flex gap-1 text-base font-light">
-                        <div class="text-blue-800">{move || article().topic.to_uppercase()}</div>
-                        "\u{b7} "
-                        {move || article().reading_time()}
-                        " min read"
-                    </div>
-                </div>
-                <div class="sm:px-16">
-                    <img
-                        src=move || article().image.url
-                        alt=move || article().image.caption
-                        class="object-cover w-full aspect-[3/2]"
-                    />
-                    <Caption>{move || article().image.caption}</Caption>
+                    <button
+                        class="px-4 py-2 text-white bg-black rounded dark:bg-white dark:text-black"
+                        disabled=posting
+                    >
+                        {move || if posting() { "Posting..." } else { "Post comment" }}
+                    </button>
                 </div>
-                <Divider />
-                <div class="flex flex-col gap-5 font-serif text-lg/[1.75rem] sm:text-xl/[2rem]
-                [&>div:first-child>p]:first-letter:text-[3.45rem]
-                sm:[&>div:first-child>p]:first-letter:text-[3.9rem]
-                [&>div:first-child>p]:first-letter:leading-none
-                [&>div:first-child>p]:first-letter:font-bold
-                [&>div:first-child>p]:first-letter:font-serif
-                [&>div:first-child>p]:first-letter:float-left
-                [&>div:first-child>p]:first-letter:pr-2">
-                    {move || {
-                        article()
-                            .fragments
-                            .iter()
-                            .map(|fragment| {
-                                match fragment {
-                                    Fragment::Image(Image { url, caption }) => {
-                                        view! {
-                                            <div class="px-16">
-                                                <img src=*url alt=*caption class="object-cover w-full" />
-                                                <Caption>{*caption}</Caption>
-                                            </div>
-                                        }
-                                    }
-                                    Fragment::Text(text) => {
-                                        view! {
-                                            <div>
-                                                <p>{*text}</p>
-                                            </div>
-                                        }
-                                    }
-                                }
-                            })
-                            .collect_view()
-                    }}
+            </form>
+            {move || {
+                comments
+                    .get()
+                    .map(|comments| match comments {
+                        Ok(comments) if comments.is_empty() => {
+                            view! {
+                                <div class="font-serif text-lg">"Be the first to comment."</div>
+                            }
+                                .into_view()
+                        }
+                        Ok(comments) => thread(&comments, None, reported, on_reply, on_flag),
+                        Err(error) => view! { <div class="font-light">{error.to_string()}</div> }.into_view(),
+                    })
+            }}
+        </div>
+    }
+}
 
+fn thread(
+    comments: &[Comment],
+    parent: Option<u64>,
+    reported: ReadSignal<Vec<u64>>,
+    on_reply: Callback<u64>,
+    on_flag: Callback<u64>,
+) -> View {
+    comments
+        .iter()
+        .filter(|comment| comment.parent == parent)
+        .sorted_by_key(|comment| comment.posted)
+        .map(|comment| {
+            let id = comment.id;
+            view! {
+                <div class="flex flex-col gap-1">
+                    <div class="flex gap-2 text-sm font-light">
+                        <span class="font-medium">{comment.author.clone()}</span>
+                        "\u{b7}"
+                        {comment.posted.with_timezone(&Local).format("%B %-d, %Y %H:%M").to_string()}
+                    </div>
+                    {if comment.hidden() {
+                        view! { <div class="italic font-light">"This comment has been hidden for review."</div> }
+                    } else {
+                        view! { <div class="font-serif text-lg">{comment.body.clone()}</div> }
+                    }}
+                    <div class="flex gap-3 text-sm font-light text-blue-800">
+                        <button on:click=move |_| on_reply(id)>"Reply"</button>
+                        <button
+                            disabled=move || reported.with(|reported| reported.contains(&id))
+                            class="disabled:opacity-50"
+                            on:click=move |_| on_flag(id)
+                        >
+                            {move || {
+                                if reported.with(|reported| reported.contains(&id)) {
+                                    "Reported"
+                                } else {
+                                    "Report"
+                                }
+                            }}
+                        </button>
+                    </div>
+                    <div class="flex flex-col gap-4 pl-4 mt-2 border-l border-neutral-300">
+                        {thread(comments, Some(id), reported, on_reply, on_flag)}
+                    </div>
                 </div>
-                <Divider />
-                <ReadMore this_article=article />
-            </div>
-        </div>
+            }
+        })
+        .collect_view()
+}
+
+fn absolute(url: &str) -> String {
+    if url.starts_with('/') {
+        format!("{}{url}", env!("SITE"))
+    } else {
+        url.to_string()
     }
 }
 
@@ -522,22 +2311,27 @@ pub fn Article() -> impl IntoView {
 pub fn Divider(#[prop(optional)] light: bool) -> impl IntoView {
     view! {
         <div
-            class="w-full h-px {}"
+            class="w-full h-px"
             class=("bg-gray-200", light)
+            class=("dark:bg-neutral-800", light)
             class=("bg-gray-800", !light)
+            class=("dark:bg-neutral-400", !light)
         ></div>
     }
 }
 
 #[component]
 pub fn ReadMore(this_article: impl Fn() -> &'static Article + 'static) -> impl IntoView {
+    let mounted = use_mounted();
     view! {
         <div class="flex flex-col gap-4">
             <Heading>"Read More"</Heading>
             <div class="flex flex-col w-full gap-4 [&_img]:w-1/4">
                 {move || {
                     let mut articles = ARTICLES.to_vec();
-                    articles.shuffle(&mut thread_rng());
+                    if mounted() {
+                        articles.shuffle(&mut thread_rng());
+                    }
                     let same_topic = articles
                         .iter()
                         .filter(|article| {
@@ -582,10 +2376,8 @@ pub fn CategoryHeading(children: Children) -> impl IntoView {
 
 #[component]
 pub fn Footer(#[prop(optional)] ads: bool) -> impl IntoView {
-    let ad = ADS.choose(&mut thread_rng()).unwrap();
-    let (show_overlay, set_show_overlay) = create_signal(false);
     view! {
-        <footer class="flex flex-col p-4 text-white bg-black">
+        <footer class="flex flex-col p-4 text-white bg-black dark:bg-neutral-900">
             <A href="/">
                 <Heading>
                     <div class="capitalize font-title">"The Waratah"</div>
@@ -595,70 +2387,397 @@ pub fn Footer(#[prop(optional)] ads: bool) -> impl IntoView {
                 <div>"Copyright \u{a9} 2024"</div>
                 "Brought to you by incredible (and a few credible) reporters."
             </div>
+            <A href=routes::AD_CHOICES.path class="text-sm underline opacity-50">
+                "Your ad choices"
+            </A>
         </footer>
         {ads
             .then_some(
                 view! {
-                    <div class="sticky bottom-0 flex justify-center w-full p-2 bg-gray-100 border">
-                        <div class="relative">
-                            <div class="relative">
-                                <img
-                                    src=format!("/images/horizontal-ads/{}", *ad)
-                                    class="h-24 cursor-pointer"
-                                />
-                                <div class=move || {
-                                    format!(
-                                        "absolute inset-0 z-10 flex flex-col items-center gap-1 p-2 bg-gray-100 border text-neutral-500 {}",
-                                        if show_overlay.get().not() {
-                                            "opacity-0 pointer-events-none"
-                                        } else {
-                                            "opacity-100 transition-opacity duration-1000"
-                                        },
-                                    )
-                                }>
+                    <div class="sticky bottom-0 flex justify-center w-full p-2 bg-gray-100 border dark:bg-neutral-800 dark:border-neutral-700">
+                        <AdSlot placement=Slot::Horizontal />
+                    </div>
+                },
+            )}
+    }
+}
+
+#[component]
+pub fn AdSlot(placement: Slot) -> impl IntoView {
+    let mounted = use_mounted();
+    let AdSettings(choices) = expect_context();
+    let track = use_analytics();
+    let ad = create_memo(move |previous: Option<&Option<&'static Ad>>| {
+        if !mounted() {
+            return None;
+        }
+        choices.with(|choices| {
+            if let Some(&Some(ad)) = previous {
+                if choices.allows(ad) {
+                    return Some(ad);
+                }
+            }
+            let active = ADS
+                .iter()
+                .filter(|ad| {
+                    ad.size == placement.size() && ad.active(today()) && choices.allows(ad)
+                })
+                .collect_vec();
+            let session = Impressions::new(SessionStorage).session();
+            pick(&active, &session, &mut thread_rng())
+        })
+    });
+    create_effect(move |recorded: Option<Option<&'static Ad>>| {
+        let ad = ad();
+        if let Some(ad) = ad.filter(|ad| recorded.flatten() != Some(*ad)) {
+            Impressions::new(SessionStorage).record(ad);
+            track(AnalyticsEvent::AdImpression {
+                advertiser: ad.advertiser.to_string(),
+                image: ad.src(),
+            });
+        }
+        ad
+    });
+    let (show_overlay, set_show_overlay) = create_signal(false);
+    let (why, set_why) = create_signal(false);
+    let close = move || {
+        set_show_overlay(false);
+        set_why(false);
+    };
+    let (image, padding) = match placement.size() {
+        Size::Horizontal => ("h-24", "px-16"),
+        Size::Vertical => ("w-40", "px-2"),
+    };
+    view! {
+        <div class="relative">
+            <div class="relative">
+                {move || {
+                    ad()
+                        .map(|ad| {
+                            view! {
+                                <A href=ad.target>
+                                    <img
+                                        src=ad.src()
+                                        alt=ad.alt
+                                        title=ad.advertiser
+                                        class=image
+                                        on:click=move |_| {
+                                            track(AnalyticsEvent::AdClick {
+                                                advertiser: ad.advertiser.to_string(),
+                                                image: ad.src(),
+                                            });
+                                        }
+                                    />
+                                </A>
+                            }
+                                .into_view()
+                        })
+                        .unwrap_or_else(|| view! { <HouseAd size=placement.size() /> })
+                }}
+                <div class=move || {
+                    format!(
+                        "absolute inset-0 z-10 flex flex-col items-center gap-1 p-2 bg-gray-100 border text-neutral-500 dark:bg-neutral-800 dark:border-neutral-700 dark:text-neutral-300 {}",
+                        if show_overlay.get().not() {
+                            "opacity-0 pointer-events-none"
+                        } else {
+                            "opacity-100 transition-opacity duration-1000"
+                        },
+                    )
+                }>
+                    <button
+                        class="absolute top-0 left-0 p-2 text-2xl leading-none"
+                        on:click=move |_| {
+                            if why() { set_why(false) } else { close() }
+                        }
+                    >
+                        "\u{2190}"
+                    </button>
+                    <h1 class="text-sm">
+                        "Ads not by " <span class="font-bold">"Google"</span>
+                    </h1>
+                    <div class=format!("flex flex-col w-full gap-1 {padding} text-xs")>
+                        {move || match (ad(), why()) {
+                            (None, _) => {
+                                view! {
+                                    <A href=routes::AD_CHOICES.path class="text-center underline">
+                                        "Your ad choices"
+                                    </A>
+                                }
+                                    .into_view()
+                            }
+                            (Some(ad), false) => {
+                                view! {
                                     <button
-                                        class="absolute top-0 left-0 p-2 text-2xl leading-none"
-                                        on:click=move |_| set_show_overlay(false)
+                                        class="w-full py-1 text-white bg-blue-500 rounded-sm shadow"
+                                        on:click=move |_| {
+                                            choices.set(AdChoices::new(LocalStorage).keep(ad.advertiser));
+                                            close();
+                                        }
                                     >
-                                        "\u{2190}"
+                                        "Keep seeing this ad"
                                     </button>
-                                    <h1 class="text-sm">
-                                        "Ads not by " <span class="font-bold">"Google"</span>
-                                    </h1>
-                                    <div class="flex flex-col w-full gap-1 px-16 text-xs">
-                                        <button
-                                            class="w-full py-1 text-white bg-blue-500 rounded-sm shadow"
-                                            on:click=move |_| set_show_overlay(false)
-                                        >
-                                            "Keep seeing this ad"
-                                        </button>
+                                    <button
+                                        class="w-full py-1 bg-white rounded-sm shadow dark:bg-neutral-700"
+                                        on:click=move |_| set_why(true)
+                                    >
+                                        "Why not this ad? \u{25B7}"
+                                    </button>
+                                }
+                                    .into_view()
+                            }
+                            (Some(ad), true) => {
+                                view! {
+                                    <button
+                                        class="w-full py-1 bg-white rounded-sm shadow dark:bg-neutral-700"
+                                        on:click=move |_| {
+                                            close();
+                                            choices
+                                                .set(AdChoices::new(LocalStorage).hide_advertiser(ad.advertiser));
+                                        }
+                                    >
+                                        {format!("Stop showing ads from {}", ad.advertiser)}
+                                    </button>
+                                    <button
+                                        class="w-full py-1 bg-white rounded-sm shadow dark:bg-neutral-700"
+                                        on:click=move |_| {
+                                            close();
+                                            choices.set(AdChoices::new(LocalStorage).hide_category(ad.category));
+                                        }
+                                    >
+                                        {format!("Stop showing {} ads", ad.category.to_lowercase())}
+                                    </button>
+                                    <A href=routes::AD_CHOICES.path class="text-center underline">
+                                        "Your ad choices"
+                                    </A>
+                                }
+                                    .into_view()
+                            }
+                        }}
+                    </div>
+                </div>
+            </div>
+            <div class="text-sm text-center opacity-50">"Advertisement"</div>
+            <button
+                class="absolute top-0 right-0 flex text-xs leading-none text-blue-500"
+                on:click=move |_| set_show_overlay(true)
+            >
+                <div class="grid border bg-gray-100/50 size-4 place-content-center">
+                    <div class="cursor-pointer border rounded-full text-[8px] aspect-square size-3 grid place-content-center border-blue-500 font-medium">
+                        i
+                    </div>
+                </div>
+                <div class="grid border place-content-center bg-gray-100/50 size-4">
+                    "X"
+                </div>
+            </button>
+        </div>
+    }
+}
+
+#[component]
+pub fn AdLanding() -> impl IntoView {
+    let params = use_params_map();
+    let page = create_memo(move |_| {
+        params.with(|params| {
+            let slug = params.get("slug")?;
+            LANDING_PAGES.iter().find(|page| page.id == slug)
+        })
+    });
+    move || {
+        let Some(page) = page() else {
+            return view! { <NotFound /> }.into_view();
+        };
+        let target = routes::AD_LANDING.path.replace(":slug", page.id);
+        let advertiser = ADS
+            .iter()
+            .find(|ad| ad.target == target)
+            .map_or(page.title, |ad| ad.advertiser);
+        view! {
+            <Title text=format!("{} | Sponsored | The Waratah", page.title) />
+            <Meta name="description" content=page.blurb />
+            <div class="w-full max-w-2xl p-4 shrink-0">
+                <div class="flex flex-col gap-4">
+                    <div class="flex flex-col gap-1">
+                        <div class="px-1 text-sm text-white w-fit bg-neutral-600 font-noto">
+                            "SPONSORED"
+                        </div>
+                        <Heading>{page.title.to_uppercase()}</Heading>
+                        <Caption>
+                            <div class="font-serif text-base text-left sm:text-lg">
+                                {page.blurb}
+                            </div>
+                        </Caption>
+                        <div class="text-base font-light">{format!("Paid for by {advertiser}")}</div>
+                    </div>
+                    <div class="sm:px-16">
+                        <img src=page.image.url alt=page.image.caption class="object-contain w-full" />
+                        <Caption>{page.image.caption}</Caption>
+                    </div>
+                    <Divider />
+                    <div class="flex flex-col gap-5 font-serif text-lg">
+                        {page
+                            .fragments
+                            .iter()
+                            .map(|fragment| view! { <ArticleFragment fragment=fragment /> })
+                            .collect_view()}
+                    </div>
+                    <Divider />
+                    <div class="text-sm font-light font-noto">
+                        "This is paid content from one of our advertisers. It was not written by The Waratah newsroom."
+                    </div>
+                </div>
+            </div>
+        }
+            .into_view()
+    }
+}
+
+#[derive(Clone)]
+struct Analytics(Rc<dyn AnalyticsSink>);
+
+fn provide_analytics() {
+    let sink: Rc<dyn AnalyticsSink> = if cfg!(debug_assertions) {
+        Rc::new(ConsoleSink)
+    } else {
+        Rc::new(HttpSink::default())
+    };
+    let handle = window_event_listener(pagehide, {
+        let sink = sink.clone();
+        move |_| sink.flush()
+    });
+    on_cleanup(move || handle.remove());
+    provide_context(Analytics(sink));
+}
+
+fn use_analytics() -> impl Fn(AnalyticsEvent) + Copy {
+    let Analytics(sink) = expect_context();
+    let sink = store_value(sink);
+    move |event| {
+        if window().navigator().do_not_track() != "1" {
+            sink.with_value(|sink| sink.record(event));
+        }
+    }
+}
+
+#[component]
+pub fn PageViews() -> impl IntoView {
+    let location = use_location();
+    let track = use_analytics();
+    create_effect(move |_| {
+        track(AnalyticsEvent::PageView {
+            path: location.pathname.get(),
+        });
+    });
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AdSettings(RwSignal<Choices>);
+
+fn provide_ad_choices() {
+    let choices = create_rw_signal(Choices::default());
+    create_effect(move |_| choices.set(AdChoices::new(LocalStorage).choices()));
+    provide_context(AdSettings(choices));
+}
+
+#[component]
+pub fn YourAdChoices() -> impl IntoView {
+    let AdSettings(choices) = expect_context();
+    let section = move |heading: &'static str,
+                        names: fn(&Choices) -> &Vec<String>,
+                        restore: fn(&AdChoices<LocalStorage>, &str) -> Choices| {
+        move || {
+            let names = choices.with(|choices| names(choices).clone());
+            (!names.is_empty()).then(|| {
+                view! {
+                    <div class="flex flex-col gap-2">
+                        <CategoryHeading>{heading}</CategoryHeading>
+                        {names
+                            .into_iter()
+                            .map(|name| {
+                                let label = name.clone();
+                                view! {
+                                    <div class="flex justify-between gap-4">
+                                        <div>{label}</div>
                                         <button
-                                            class="w-full py-1 bg-white rounded-sm shadow"
-                                            on:click=move |_| set_show_overlay(false)
+                                            class="text-sm font-light text-blue-800"
+                                            on:click=move |_| {
+                                                choices.set(restore(&AdChoices::new(LocalStorage), &name));
+                                            }
                                         >
-                                            "Why not this ad? \u{25B7}"
+                                            "Undo"
                                         </button>
                                     </div>
-                                </div>
-                            </div>
-                            <div class="text-sm text-center opacity-50">"Advertisement"</div>
-                            <button
-                                class="absolute top-0 right-0 flex text-xs leading-none text-blue-500"
-                                on:click=move |_| set_show_overlay(true)
-                            >
-                                <div class="grid border bg-gray-100/50 size-4 place-content-center">
-                                    <div class="cursor-pointer border rounded-full text-[8px] aspect-square size-3 grid place-content-center border-blue-500 font-medium">
-                                        i
-                                    </div>
-                                </div>
-                                <div class="grid border place-content-center bg-gray-100/50 size-4">
-                                    "X"
-                                </div>
-                            </button>
-                        </div>
+                                }
+                            })
+                            .collect_view()}
                     </div>
+                }
+            })
+        }
+    };
+    view! {
+        <Title text="Your ad choices | The Waratah" />
+        <div class="w-full max-w-2xl p-4 shrink-0">
+            <div class="flex flex-col gap-4 font-noto">
+                <Heading>"Your ad choices"</Heading>
+                <Divider />
+                {move || {
+                    choices
+                        .with(Choices::is_empty)
+                        .then(|| {
+                            view! {
+                                <div class="font-serif text-lg">
+                                    "You haven't made any ad choices. Use the \u{24d8} on an ad to keep seeing it or hide it."
+                                </div>
+                            }
+                        })
+                }}
+                {section(
+                    "Advertisers you keep seeing, even in hidden categories",
+                    |choices| &choices.kept,
+                    AdChoices::restore_advertiser,
+                )}
+                {section("Hidden advertisers", |choices| &choices.advertisers, AdChoices::restore_advertiser)}
+                {section("Hidden categories", |choices| &choices.categories, AdChoices::restore_category)}
+                {move || {
+                    choices
+                        .with(Choices::is_empty)
+                        .not()
+                        .then(|| {
+                            view! {
+                                <button
+                                    class="self-start px-4 py-2 text-white bg-black rounded dark:bg-white dark:text-black"
+                                    on:click=move |_| choices.set(AdChoices::new(LocalStorage).reset())
+                                >
+                                    "Reset all"
+                                </button>
+                            }
+                        })
+                }}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn HouseAd(size: Size) -> impl IntoView {
+    let href = routes::CROSSWORD
+        .path
+        .replace(":id", &CROSSWORDS.len().saturating_sub(1).to_string());
+    view! {
+        <A
+            href=href
+            class=format!(
+                "flex flex-col items-center justify-center gap-2 p-4 text-center text-white bg-blue-800 font-noto {}",
+                match size {
+                    Size::Horizontal => "h-24 w-[26rem] max-w-full",
+                    Size::Vertical => "w-40 h-[37.5rem]",
                 },
-            )}
+            )
+        >
+            <div class="font-serif text-2xl">"Stuck for something to do?"</div>
+            <div class="text-sm">"Try The Waratah crossword \u{2192}"</div>
+        </A>
     }
 }
 
@@ -860,7 +2979,7 @@ pub fn CrosswordGrid(
                         cell.map_or_else(
                             || {
                                 view! {
-                                    <div class="bg-black">
+                                    <div class="bg-black dark:bg-neutral-600">
                                         <button
                                             class="size-full"
                                             on:click=move |_| {
@@ -875,6 +2994,7 @@ pub fn CrosswordGrid(
                                 view! {
                                     <div
                                         class=("bg-yellow-200", move || selected() == Some(index))
+                                        class=("dark:bg-yellow-800", move || selected() == Some(index))
                                         class=(
                                             "bg-blue-200",
                                             move || {
@@ -893,7 +3013,7 @@ pub fn CrosswordGrid(
                                                     .map_or(false, |word| word.contains(position(index)))
                                             },
                                         )
-                                        class="relative text-xl border border-black size-8"
+                                        class="relative text-xl border border-black dark:border-neutral-400 size-8"
                                     >
 
                                         <input
@@ -927,14 +3047,30 @@ pub fn CrosswordGrid(
 }
 
 #[component]
-#[allow(clippy::too_many_lines)]
 pub fn Crossword() -> impl IntoView {
-    let crossword = || -> &Crossword {
-        &CROSSWORDS[use_params_map()
-            .with(|params| <usize as FromStr>::from_str(params.get("id").unwrap()).unwrap())]
-    };
+    let params = use_params_map();
+    let id = create_memo(move |_| {
+        params.with(|params| {
+            params
+                .get("id")
+                .and_then(|id| <usize as FromStr>::from_str(id).ok())
+                .filter(|id| *id < CROSSWORDS.len())
+        })
+    });
+    move || {
+        id().map_or_else(
+            || view! { <NotFound /> }.into_view(),
+            |id| view! { <CrosswordPuzzle id=id /> }.into_view(),
+        )
+    }
+}
+
+#[component]
+#[allow(clippy::too_many_lines)]
+pub fn CrosswordPuzzle(id: usize) -> impl IntoView {
+    let crossword: &Crossword = &CROSSWORDS[id];
     let starts = move || {
-        crossword()
+        crossword
             .words
             .iter()
             .map(|word| word.position)
@@ -944,12 +3080,12 @@ pub fn Crossword() -> impl IntoView {
     };
     let grid = {
         move || {
-            let size = crossword().size();
+            let size = crossword.size();
             (0..size.y)
                 .flat_map(|y| {
                     (0..size.x)
                         .map(|x| {
-                            crossword()
+                            crossword
                                 .to_letters()
                                 .iter()
                                 .find(|letter| letter.position == Vec2 { x, y })
@@ -976,26 +3112,35 @@ pub fn Crossword() -> impl IntoView {
                     Some((char, _)) => letter == &Some(*char),
                 })
     });
+    let track = use_analytics();
+    create_effect(move |previous: Option<bool>| {
+        let correct = correct();
+        if correct && previous == Some(false) {
+            track(AnalyticsEvent::CrosswordCompleted { crossword: id });
+        }
+        correct
+    });
     let check = move |event: MouseEvent| {
         let button: HtmlButtonElement = event_target(&event);
         button.set_text_content(Some(format!("{}", correct()).as_str()));
     };
     let (selected, set_selected) = create_signal(None::<Word>);
     view! {
+        <Title text=format!("Crossword #{id} | The Waratah") />
         <div class="flex flex-col w-full gap-4 p-4 lg:flex-row">
             <div class="flex flex-col gap-2 lg:basis-0 lg:grow">
                 {move || {
                     view! {
                         <CrosswordGrid
                             grid=grid()
-                            crossword=crossword()
+                            crossword=crossword
                             on_solution_change=set_solution
                             on_selection_change=set_selected
                         />
                     }
                 }} <div class="flex justify-center has-[:disabled]:hidden">
                     <button
-                        class="px-4 py-2 text-white bg-black rounded"
+                        class="px-4 py-2 text-white bg-black rounded dark:bg-white dark:text-black"
                         disabled=move || {
                             solution().is_empty()
                                 || solution().iter().any(|(_, letter)| letter.is_none())
@@ -1019,7 +3164,7 @@ pub fn Crossword() -> impl IntoView {
                                             {direction.to_string()}
                                         </h1>
                                         <div class="grid grid-cols-[auto_minmax(0,1fr)]">
-                                            {crossword()
+                                            {crossword
                                                 .words
                                                 .iter()
                                                 .filter(|word| word.direction == *direction)
@@ -1032,16 +3177,17 @@ pub fn Crossword() -> impl IntoView {
                                                         <div
                                                             class="pr-2 font-semibold"
                                                             class=("bg-blue-200", current)
+                                                            class=("dark:bg-blue-900", current)
                                                         >
                                                             {starts()
                                                                 .iter()
                                                                 .position(|start| *start == word.position)
                                                                 .map(|index| index + 1)}
                                                         </div>
-                                                        <div class=(
-                                                            "bg-blue-200",
-                                                            current,
-                                                        )>{format!("{} ({})", word.clue, word.answer.len())}</div>
+                                                        <div
+                                                            class=("bg-blue-200", current)
+                                                            class=("dark:bg-blue-900", current)
+                                                        >{format!("{} ({})", word.clue, word.answer.len())}</div>
                                                     }
                                                 })
                                                 .collect_view()}
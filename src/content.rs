@@ -0,0 +1,135 @@
+use std::{cmp::Reverse, io::Read};
+
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use nom::{combinator::all_consuming, IResult};
+
+use crate::ad::Ad;
+use crate::article::Article;
+use crate::classified::Classified;
+use crate::crossword::Crossword;
+use crate::event::Event;
+
+fn load<T>(
+    mut data: &'static str,
+    parse: fn(&'static str) -> IResult<&'static str, T>,
+) -> Result<Vec<T>> {
+    let mut items = Vec::new();
+    while !data.is_empty() {
+        let (length, rest) = data
+            .split_once(' ')
+            .ok_or_else(|| anyhow!("invalid data"))?;
+        let length: usize = length.parse()?;
+        let (_, item) = all_consuming(parse)(&rest[..length])?;
+        items.push(item);
+        data = rest
+            .get(length + 1..)
+            .or_else(|| rest.get(length..))
+            .unwrap();
+    }
+    Ok(items)
+}
+
+lazy_static! {
+    pub static ref ADS: &'static [Ad] =
+        load(include_str!(concat!(env!("OUT_DIR"), "/ads")), Ad::parse)
+            .unwrap()
+            .leak();
+    pub static ref ARTICLES: &'static [Article] = {
+        let mut articles = load(
+            include_str!(concat!(env!("OUT_DIR"), "/articles")),
+            Article::parse,
+        )
+        .unwrap();
+        articles.sort_unstable_by_key(|article| -(article.index as i128));
+        articles.leak()
+    };
+    pub static ref LANDING_PAGES: &'static [Article] = load(
+        include_str!(concat!(env!("OUT_DIR"), "/landing-pages")),
+        Article::parse,
+    )
+    .unwrap()
+    .leak();
+    pub static ref CLASSIFIEDS: &'static [Classified] = {
+        let mut classifieds = load(
+            include_str!(concat!(env!("OUT_DIR"), "/classifieds")),
+            Classified::parse,
+        )
+        .unwrap();
+        classifieds.sort_unstable_by_key(|classified| Reverse(classified.posted));
+        classifieds.leak()
+    };
+    pub static ref EVENTS: &'static [Event] = {
+        let mut events = load(
+            include_str!(concat!(env!("OUT_DIR"), "/events")),
+            Event::parse,
+        )
+        .unwrap();
+        events.sort_unstable_by_key(|event| event.start);
+        events.leak()
+    };
+    pub static ref CROSSWORDS: &'static [Crossword] = {
+        let data = String::from_utf8(
+            include_bytes!(concat!(env!("OUT_DIR"), "/crosswords"))
+                .bytes()
+                .map(Result::unwrap)
+                .map(u8::reverse_bits)
+                .collect(),
+        )
+        .unwrap()
+        .leak();
+        let crosswords: Vec<_> = data.split("\n\n").map(Crossword::parse).collect();
+        crosswords.leak()
+    };
+    pub static ref REDIRECTS: &'static [(&'static str, &'static str)] =
+        include_str!(concat!(env!("OUT_DIR"), "/redirects"))
+            .lines()
+            .map(|line| line.split_once(' ').unwrap())
+            .collect_vec()
+            .leak();
+}
+
+pub fn find_article(id: &str) -> Option<&'static Article> {
+    let id = REDIRECTS
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(id))
+        .map_or(id, |(_, target)| target);
+    ARTICLES
+        .iter()
+        .find(|article| article.id.eq_ignore_ascii_case(id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_article, ARTICLES, CROSSWORDS, EVENTS, LANDING_PAGES};
+    use crate::routes::pages;
+
+    #[test]
+    fn ids_are_lowercase_and_prerendered() {
+        let pages = pages(&ARTICLES, CROSSWORDS.len(), &EVENTS, &LANDING_PAGES);
+        for article in ARTICLES.iter() {
+            assert_eq!(article.id, article.id.to_lowercase());
+            let path = format!("/articles/{}", article.id);
+            assert!(pages.iter().any(|page| page.path == path), "{path}");
+        }
+    }
+
+    #[test]
+    fn finds_articles_ignoring_case() {
+        for id in ["ev-dealers-charge-less", "EV-dealers-charge-less"] {
+            assert_eq!(
+                find_article(id).map(|article| article.id),
+                Some("ev-dealers-charge-less")
+            );
+        }
+    }
+
+    #[test]
+    fn follows_redirects() {
+        assert_eq!(
+            find_article("egg-prices-rise-as-chickens-refuse-to-work").map(|article| article.id),
+            Some("egg-prices-rise-as-chickens-stop-working")
+        );
+    }
+}
@@ -1,18 +1,18 @@
 use std::collections::HashSet;
-use std::io::Read;
 use std::ops::{Add, AddAssign, Sub, SubAssign};
 use std::str::FromStr;
 
-use lazy_static::lazy_static;
+use serde::{Serialize, Serializer};
 use strum::{Display, VariantArray};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Crossword {
     pub words: Vec<Word>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub struct Word {
+    #[serde(rename = "length", serialize_with = "length")]
     pub answer: &'static str,
     pub clue: &'static str,
     pub direction: Direction,
@@ -38,13 +38,18 @@ impl Word {
     }
 }
 
+fn length<S: Serializer>(answer: &&'static str, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(answer.len() as u64)
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Letter {
     pub character: char,
     pub position: Vec2,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, VariantArray, Display, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, VariantArray, Display, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Direction {
     #[default]
     Across,
@@ -62,7 +67,7 @@ impl Direction {
 }
 
 impl Crossword {
-    fn from_str(s: &'static str) -> Self {
+    pub fn parse(s: &'static str) -> Self {
         Self {
             words: s
                 .trim()
@@ -130,7 +135,7 @@ impl Crossword {
         size
     }
 }
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash, Serialize)]
 pub struct Vec2 {
     pub x: usize,
     pub y: usize,
@@ -167,19 +172,3 @@ impl SubAssign for Vec2 {
         *self = *self - rhs;
     }
 }
-
-lazy_static! {
-    pub static ref CROSSWORDS: &'static [Crossword] = {
-        let data = String::from_utf8(
-            include_bytes!(concat!(env!("OUT_DIR"), "/crosswords"))
-                .bytes()
-                .map(Result::unwrap)
-                .map(u8::reverse_bits)
-                .collect(),
-        )
-        .unwrap()
-        .leak();
-        let crosswords: Vec<_> = data.split("\n\n").map(Crossword::from_str).collect();
-        crosswords.leak()
-    };
-}
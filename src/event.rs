@@ -0,0 +1,171 @@
+use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use itertools::Itertools;
+use nom::{
+    bytes::complete::{tag, take_until1},
+    combinator::{map, map_res, rest},
+    sequence::{preceded, tuple},
+    IResult,
+};
+use serde::Serialize;
+
+pub const UTC_OFFSET_HOURS: i32 = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Event {
+    pub id: &'static str,
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    pub venue: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+}
+
+impl Event {
+    pub fn parse(input: &'static str) -> IResult<&'static str, Self> {
+        let datetime = |input| {
+            map_res(take_until1("\n"), |input: &str| {
+                NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M")
+            })(input)
+        };
+        map(
+            tuple((
+                take_until1(" "),
+                preceded(tag(" "), datetime),
+                preceded(tag("\n"), datetime),
+                preceded(tag("\n"), take_until1("\n")),
+                preceded(tag("\n"), take_until1("\n")),
+                preceded(tag("\n"), rest),
+            )),
+            |(id, start, end, venue, title, description)| Self {
+                id,
+                start,
+                end,
+                venue,
+                title,
+                description,
+            },
+        )(input)
+    }
+
+    fn utc(time: NaiveDateTime) -> DateTime<Utc> {
+        FixedOffset::east_opt(UTC_OFFSET_HOURS * 60 * 60)
+            .unwrap()
+            .from_local_datetime(&time)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    pub fn ics(&self, site: &str) -> String {
+        const FORMAT: &str = "%Y%m%dT%H%M%SZ";
+        [
+            "BEGIN:VEVENT".to_string(),
+            format!(
+                "UID:{}@{}",
+                self.id,
+                site.split("://").last().unwrap_or(site)
+            ),
+            format!("DTSTAMP:{}", Self::utc(self.start).format(FORMAT)),
+            format!("DTSTART:{}", Self::utc(self.start).format(FORMAT)),
+            format!("DTEND:{}", Self::utc(self.end).format(FORMAT)),
+            format!("SUMMARY:{}", escape(self.title)),
+            format!("LOCATION:{}", escape(self.venue)),
+            format!("DESCRIPTION:{}", escape(self.description)),
+            format!("URL:{site}/events/{}", self.id),
+            "END:VEVENT".to_string(),
+        ]
+        .iter()
+        .map(|line| fold(line))
+        .join("")
+    }
+}
+
+pub fn calendar<'a>(name: &str, site: &str, events: impl IntoIterator<Item = &'a Event>) -> String {
+    [
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:-//{name}//Events//EN"),
+        format!("X-WR-CALNAME:{}", escape(name)),
+    ]
+    .iter()
+    .map(|line| fold(line))
+    .chain(events.into_iter().map(|event| event.ics(site)))
+    .chain([fold("END:VCALENDAR")])
+    .join("")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+    for char in line.chars() {
+        if length + char.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(char);
+        length += char.len_utf8();
+    }
+    folded + "\r\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{escape, fold, Event};
+
+    fn unfold(text: &str) -> String {
+        text.replace("\r\n ", "")
+    }
+
+    #[test]
+    fn escapes_text() {
+        assert_eq!(
+            escape("Pies, sauce; and\\or\nchips"),
+            r"Pies\, sauce\; and\\or\nchips"
+        );
+    }
+
+    #[test]
+    fn folds_at_75_octets() {
+        let line = format!("DESCRIPTION:{}", "é".repeat(100));
+        let folded = fold(&line);
+        assert!(folded.ends_with("\r\n"));
+        for part in folded.trim_end_matches("\r\n").split("\r\n") {
+            assert!(part.len() <= 75, "{part:?}");
+        }
+        assert_eq!(unfold(&folded), format!("{line}\r\n"));
+        assert_eq!(fold("SHORT"), "SHORT\r\n");
+    }
+
+    #[test]
+    fn ics_escapes_fields_and_converts_to_utc() {
+        let start = NaiveDate::from_ymd_opt(2024, 8, 3)
+            .unwrap()
+            .and_hms_opt(9, 30, 0)
+            .unwrap();
+        let event = Event {
+            id: "sizzle",
+            start,
+            end: start + chrono::Duration::hours(2),
+            venue: "Warrah Oval, Gate 2",
+            title: "Sausage sizzle; fundraiser",
+            description: "Bring cash.\nOnions extra.",
+        };
+        let ics = unfold(&event.ics("https://example.com"));
+        let lines = ics.split("\r\n").collect::<Vec<_>>();
+        assert!(lines.contains(&"UID:sizzle@example.com"));
+        assert!(lines.contains(&"DTSTAMP:20240803T013000Z"));
+        assert!(lines.contains(&"DTSTART:20240803T013000Z"));
+        assert!(lines.contains(&"DTEND:20240803T033000Z"));
+        assert!(lines.contains(&r"SUMMARY:Sausage sizzle\; fundraiser"));
+        assert!(lines.contains(&r"LOCATION:Warrah Oval\, Gate 2"));
+        assert!(lines.contains(&r"DESCRIPTION:Bring cash.\nOnions extra."));
+    }
+}
@@ -0,0 +1,157 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::storage::Storage;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub id: String,
+    pub progress: f64,
+}
+
+impl Entry {
+    pub fn finished(&self) -> bool {
+        self.progress >= 0.9
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+struct Visits {
+    previous: Option<NaiveDate>,
+    last: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone)]
+pub struct History<S> {
+    storage: S,
+}
+
+impl<S: Storage> History<S> {
+    const ENTRIES: &'static str = "history";
+    const VISITS: &'static str = "visits";
+
+    pub const fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    pub fn entries(&self) -> Vec<Entry> {
+        self.storage.load(Self::ENTRIES)
+    }
+
+    pub fn open(&self, id: &str) {
+        let mut entries = self.entries();
+        let entry = entries.iter().position(|entry| entry.id == id).map_or_else(
+            || Entry {
+                id: id.to_string(),
+                progress: 0.0,
+            },
+            |index| entries.remove(index),
+        );
+        entries.insert(0, entry);
+        self.storage.save(Self::ENTRIES, &entries);
+    }
+
+    pub fn record_progress(&self, id: &str, progress: f64) {
+        let mut entries = self.entries();
+        if let Some(entry) = entries.iter_mut().find(|entry| entry.id == id) {
+            entry.progress = entry.progress.max(progress.clamp(0.0, 1.0));
+            self.storage.save(Self::ENTRIES, &entries);
+        }
+    }
+
+    pub fn clear(&self) {
+        self.storage.remove(Self::ENTRIES);
+        self.storage.remove(Self::VISITS);
+    }
+
+    pub fn visit(&self, today: NaiveDate) -> Option<NaiveDate> {
+        let mut visits: Visits = self.storage.load(Self::VISITS);
+        if visits.last != Some(today) {
+            visits = Visits {
+                previous: visits.last,
+                last: Some(today),
+            };
+            self.storage.save(Self::VISITS, &visits);
+        }
+        visits.previous
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{Entry, History};
+    use crate::storage::MemoryStorage;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 8, day).unwrap()
+    }
+
+    #[test]
+    fn finished_from_ninety_percent() {
+        let entry = |progress| Entry {
+            id: "a".to_string(),
+            progress,
+        };
+        assert!(!entry(0.85).finished());
+        assert!(entry(0.9).finished());
+        assert!(entry(1.0).finished());
+    }
+
+    #[test]
+    fn open_moves_the_article_to_the_front_and_keeps_progress() {
+        let history = History::new(MemoryStorage::default());
+        history.open("a");
+        history.record_progress("a", 0.5);
+        history.open("b");
+        history.open("a");
+        let entries = history.entries();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.id.as_str())
+                .collect::<Vec<_>>(),
+            ["a", "b"]
+        );
+        assert_eq!(entries[0].progress, 0.5);
+    }
+
+    #[test]
+    fn progress_only_grows_and_is_clamped() {
+        let history = History::new(MemoryStorage::default());
+        history.open("a");
+        history.record_progress("a", 0.6);
+        history.record_progress("a", 0.3);
+        assert_eq!(history.entries()[0].progress, 0.6);
+        history.record_progress("a", 1.4);
+        assert_eq!(history.entries()[0].progress, 1.0);
+    }
+
+    #[test]
+    fn progress_for_unopened_articles_is_ignored() {
+        let history = History::new(MemoryStorage::default());
+        history.record_progress("a", 0.5);
+        assert!(history.entries().is_empty());
+    }
+
+    #[test]
+    fn clear_forgets_everything_read() {
+        let history = History::new(MemoryStorage::default());
+        history.open("a");
+        history.visit(date(1));
+        history.clear();
+        assert!(history.entries().is_empty());
+        assert_eq!(history.visit(date(2)), None);
+    }
+
+    #[test]
+    fn visit_returns_the_previous_day_visited() {
+        let history = History::new(MemoryStorage::default());
+        assert_eq!(history.visit(date(1)), None);
+        assert_eq!(history.visit(date(1)), None);
+        assert_eq!(history.visit(date(3)), Some(date(1)));
+        assert_eq!(history.visit(date(3)), Some(date(1)));
+        assert_eq!(history.visit(date(4)), Some(date(3)));
+    }
+}
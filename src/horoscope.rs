@@ -0,0 +1,178 @@
+use chrono::{Datelike, NaiveDate};
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use strum::{Display, VariantArray};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, VariantArray)]
+pub enum Sign {
+    Aries,
+    Taurus,
+    Gemini,
+    Cancer,
+    Leo,
+    Virgo,
+    Libra,
+    Scorpio,
+    Sagittarius,
+    Capricorn,
+    Aquarius,
+    Pisces,
+}
+
+impl Sign {
+    pub fn of(date: NaiveDate) -> Self {
+        const STARTS: [(u32, u32, Sign); 12] = [
+            (1, 20, Sign::Aquarius),
+            (2, 19, Sign::Pisces),
+            (3, 21, Sign::Aries),
+            (4, 20, Sign::Taurus),
+            (5, 21, Sign::Gemini),
+            (6, 21, Sign::Cancer),
+            (7, 23, Sign::Leo),
+            (8, 23, Sign::Virgo),
+            (9, 23, Sign::Libra),
+            (10, 23, Sign::Scorpio),
+            (11, 22, Sign::Sagittarius),
+            (12, 22, Sign::Capricorn),
+        ];
+        STARTS
+            .iter()
+            .rev()
+            .find(|(month, day, _)| (date.month(), date.day()) >= (*month, *day))
+            .map_or(Self::Capricorn, |(_, _, sign)| *sign)
+    }
+
+    pub const fn symbol(self) -> &'static str {
+        match self {
+            Self::Aries => "\u{2648}",
+            Self::Taurus => "\u{2649}",
+            Self::Gemini => "\u{264a}",
+            Self::Cancer => "\u{264b}",
+            Self::Leo => "\u{264c}",
+            Self::Virgo => "\u{264d}",
+            Self::Libra => "\u{264e}",
+            Self::Scorpio => "\u{264f}",
+            Self::Sagittarius => "\u{2650}",
+            Self::Capricorn => "\u{2651}",
+            Self::Aquarius => "\u{2652}",
+            Self::Pisces => "\u{2653}",
+        }
+    }
+
+    pub const fn dates(self) -> &'static str {
+        match self {
+            Self::Aries => "March 21 to April 19",
+            Self::Taurus => "April 20 to May 20",
+            Self::Gemini => "May 21 to June 20",
+            Self::Cancer => "June 21 to July 22",
+            Self::Leo => "July 23 to August 22",
+            Self::Virgo => "August 23 to September 22",
+            Self::Libra => "September 23 to October 22",
+            Self::Scorpio => "October 23 to November 21",
+            Self::Sagittarius => "November 22 to December 21",
+            Self::Capricorn => "December 22 to January 19",
+            Self::Aquarius => "January 20 to February 18",
+            Self::Pisces => "February 19 to March 20",
+        }
+    }
+}
+
+const OPENINGS: &[&str] = &[
+    "The moon is in your sixth house, which is odd, because you only have the one.",
+    "Mercury is in retrograde, and so is the Warrah bus timetable.",
+    "The stars have aligned, mostly over the servo on the highway.",
+    "Venus is rising, unlike the price of your house.",
+    "Jupiter is looking at you funny.",
+    "Saturn has returned, and would like its tupperware back.",
+    "The planets are in a rare formation not seen since the last council meeting.",
+];
+
+const PREDICTIONS: &[&str] = &[
+    "A tall, dark stranger will ask to borrow your ladder.",
+    "You will find money in an old pair of shorts, but it will be the Monopoly kind.",
+    "A chicken will look at you with something like respect.",
+    "Someone at the surf club will finally remember your name, and get it wrong.",
+    "The fish will not be biting, but the sandflies will.",
+    "You will be tempted to reply-all. Resist.",
+    "A long-lost cousin will get in touch about a business opportunity involving eggs.",
+    "The wind will change, and your face will stay like that.",
+    "You will step on a Lego brick you did not know you owned.",
+];
+
+const ADVICE: &[&str] = &[
+    "Avoid major decisions until after smoko.",
+    "Now is a good time to check the tyre pressure.",
+    "Trust your gut, but check its sources.",
+    "Say yes to the sausage sizzle.",
+    "Keep your thongs close and your towel closer.",
+    "Do not, under any circumstances, read the comments.",
+    "Wear sunscreen. The stars insist.",
+];
+
+const COLOURS: &[&str] = &[
+    "beige",
+    "hi-vis orange",
+    "thong blue",
+    "sunburn pink",
+    "ute white",
+    "eucalyptus green",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Horoscope {
+    pub sign: Sign,
+    pub reading: String,
+    pub lucky_number: u8,
+    pub lucky_colour: &'static str,
+}
+
+pub fn horoscope(sign: Sign, date: NaiveDate) -> Horoscope {
+    let index = Sign::VARIANTS
+        .iter()
+        .position(|other| *other == sign)
+        .unwrap();
+    let mut rng = ChaCha8Rng::seed_from_u64(
+        u64::try_from(date.num_days_from_ce()).unwrap_or_default() * 12 + index as u64,
+    );
+    let mut pick = |phrases: &[&'static str]| *phrases.choose(&mut rng).unwrap();
+    let reading = [pick(OPENINGS), pick(PREDICTIONS), pick(ADVICE)].join(" ");
+    Horoscope {
+        sign,
+        reading,
+        lucky_colour: pick(COLOURS),
+        lucky_number: rng.gen_range(1..=99),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{horoscope, Horoscope, Sign};
+
+    #[test]
+    fn signs_change_on_their_start_dates() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+        assert_eq!(Sign::of(date(3, 20)), Sign::Pisces);
+        assert_eq!(Sign::of(date(3, 21)), Sign::Aries);
+        assert_eq!(Sign::of(date(1, 19)), Sign::Capricorn);
+        assert_eq!(Sign::of(date(12, 22)), Sign::Capricorn);
+    }
+
+    #[test]
+    fn readings_are_stable() {
+        let date = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+        assert_eq!(
+            horoscope(Sign::Leo, date),
+            Horoscope {
+                sign: Sign::Leo,
+                reading: "Mercury is in retrograde, and so is the Warrah bus timetable. \
+                    You will be tempted to reply-all. Resist. \
+                    Keep your thongs close and your towel closer."
+                    .to_string(),
+                lucky_number: 25,
+                lucky_colour: "ute white",
+            }
+        );
+    }
+}
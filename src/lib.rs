@@ -0,0 +1,22 @@
+pub mod ad;
+pub mod ad_choices;
+pub mod analytics;
+pub mod article;
+pub mod bookmarks;
+pub mod classified;
+pub mod comments;
+#[allow(non_snake_case)]
+pub mod components;
+pub mod content;
+pub mod crossword;
+pub mod event;
+pub mod history;
+pub mod horoscope;
+pub mod paywall;
+pub mod polls;
+pub mod preferences;
+pub mod rotation;
+pub mod routes;
+pub mod share;
+pub mod storage;
+pub mod subscriptions;
@@ -0,0 +1,190 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{article::Article, storage::Storage};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Meter {
+    pub allowance: usize,
+    pub exempt_topics: &'static [&'static str],
+    pub search_engines: &'static [&'static str],
+    pub preview_host: &'static str,
+}
+
+pub const METER: Meter = Meter {
+    allowance: 5,
+    exempt_topics: &["Local News"],
+    search_engines: &[
+        "google.",
+        "bing.com",
+        "duckduckgo.com",
+        "search.yahoo.com",
+        "ecosia.org",
+        "baidu.com",
+        "yandex.",
+    ],
+    preview_host: "drafts.",
+};
+
+impl Meter {
+    pub fn exempt(&self, article: &Article) -> bool {
+        self.exempt_topics.contains(&article.topic)
+    }
+
+    pub fn bypassed(&self, referrer: &str, host: &str) -> bool {
+        let referrer = referrer
+            .split_once("://")
+            .map_or(referrer, |(_, rest)| rest)
+            .split('/')
+            .next()
+            .unwrap_or_default();
+        self.search_engines
+            .iter()
+            .any(|engine| referrer.starts_with(engine) || referrer.contains(&format!(".{engine}")))
+            || host.starts_with(self.preview_host)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+struct Month {
+    month: String,
+    articles: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Paywall<S> {
+    storage: S,
+    meter: Meter,
+}
+
+impl<S: Storage> Paywall<S> {
+    const KEY: &'static str = "meter";
+
+    pub const fn new(storage: S, meter: Meter) -> Self {
+        Self { storage, meter }
+    }
+
+    fn month(&self, today: NaiveDate) -> Month {
+        let month = today.format("%Y-%m").to_string();
+        let stored: Month = self.storage.load(Self::KEY);
+        if stored.month == month {
+            stored
+        } else {
+            Month {
+                month,
+                articles: Vec::new(),
+            }
+        }
+    }
+
+    pub fn read(&self, today: NaiveDate) -> usize {
+        self.month(today).articles.len()
+    }
+
+    pub fn open(&self, article: &Article, today: NaiveDate) -> bool {
+        if self.meter.exempt(article) {
+            return true;
+        }
+        let mut month = self.month(today);
+        if month.articles.iter().any(|id| id == article.id) {
+            return true;
+        }
+        if month.articles.len() >= self.meter.allowance {
+            return false;
+        }
+        month.articles.push(article.id.to_string());
+        self.storage.save(Self::KEY, &month);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{Paywall, METER};
+    use crate::{
+        article::{Article, Image},
+        storage::MemoryStorage,
+    };
+
+    const IDS: [&str; 7] = ["a", "b", "c", "d", "e", "f", "g"];
+
+    fn article(id: &'static str, topic: &'static str) -> Article {
+        Article {
+            id,
+            topic,
+            index: 0,
+            date: date(1, 1),
+            blurb: "",
+            title: id,
+            image: Image {
+                url: "",
+                caption: "",
+            },
+            fragments: Vec::new(),
+        }
+    }
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    #[test]
+    fn closes_after_the_allowance() {
+        let paywall = Paywall::new(MemoryStorage::default(), METER);
+        for id in &IDS[..METER.allowance] {
+            assert!(paywall.open(&article(id, "National"), date(8, 1)));
+        }
+        assert!(!paywall.open(&article(IDS[METER.allowance], "National"), date(8, 2)));
+        assert_eq!(paywall.read(date(8, 2)), METER.allowance);
+    }
+
+    #[test]
+    fn rereading_is_free() {
+        let paywall = Paywall::new(MemoryStorage::default(), METER);
+        for id in &IDS[..METER.allowance] {
+            paywall.open(&article(id, "National"), date(8, 1));
+        }
+        assert!(paywall.open(&article(IDS[0], "National"), date(8, 3)));
+        assert_eq!(paywall.read(date(8, 3)), METER.allowance);
+    }
+
+    #[test]
+    fn exempt_topics_are_not_counted() {
+        let paywall = Paywall::new(MemoryStorage::default(), METER);
+        for id in &IDS[..METER.allowance] {
+            paywall.open(&article(id, "National"), date(8, 1));
+        }
+        assert!(paywall.open(&article(IDS[6], METER.exempt_topics[0]), date(8, 1)));
+        assert_eq!(paywall.read(date(8, 1)), METER.allowance);
+    }
+
+    #[test]
+    fn resets_each_month() {
+        let paywall = Paywall::new(MemoryStorage::default(), METER);
+        for id in &IDS[..METER.allowance] {
+            paywall.open(&article(id, "National"), date(8, 31));
+        }
+        assert_eq!(paywall.read(date(9, 1)), 0);
+        assert!(paywall.open(&article(IDS[6], "National"), date(9, 1)));
+        assert_eq!(paywall.read(date(9, 1)), 1);
+    }
+
+    #[test]
+    fn search_engines_bypass_the_meter() {
+        assert!(METER.bypassed("https://www.google.com.au/", "thewaratah.pages.dev"));
+        assert!(METER.bypassed("https://duckduckgo.com/?q=waratah", "thewaratah.pages.dev"));
+    }
+
+    #[test]
+    fn other_referrers_are_metered() {
+        assert!(!METER.bypassed("https://example.com/google.com", "thewaratah.pages.dev"));
+        assert!(!METER.bypassed("", "thewaratah.pages.dev"));
+    }
+
+    #[test]
+    fn drafts_host_bypasses_the_meter() {
+        assert!(METER.bypassed("", "drafts.thewaratah.pages.dev"));
+    }
+}
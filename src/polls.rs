@@ -0,0 +1,177 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    future::Future,
+};
+
+use crate::storage::Storage;
+
+pub type Results = HashMap<String, usize>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PollError {
+    AlreadyVoted,
+    Unavailable(String),
+}
+
+impl Display for PollError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::AlreadyVoted => write!(f, "You have already voted in this poll."),
+            Self::Unavailable(reason) => {
+                write!(f, "Poll results are unavailable right now ({reason}).")
+            }
+        }
+    }
+}
+
+pub trait PollBackend {
+    fn results(&self, poll: &str) -> impl Future<Output = Result<Results, PollError>>;
+    fn vote(&self, poll: &str, option: &str) -> impl Future<Output = Result<Results, PollError>>;
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalPolls<S> {
+    storage: S,
+}
+
+impl<S: Storage> LocalPolls<S> {
+    const KEY: &'static str = "poll-results";
+
+    pub const fn new(storage: S) -> Self {
+        Self { storage }
+    }
+}
+
+impl<S: Storage + Clone> PollBackend for LocalPolls<S> {
+    async fn results(&self, poll: &str) -> Result<Results, PollError> {
+        let mut polls: HashMap<String, Results> = self.storage.load(Self::KEY);
+        Ok(polls.remove(poll).unwrap_or_default())
+    }
+
+    async fn vote(&self, poll: &str, option: &str) -> Result<Results, PollError> {
+        if !Ballots::new(self.storage.clone()).record(poll, option) {
+            return Err(PollError::AlreadyVoted);
+        }
+        let mut polls: HashMap<String, Results> = self.storage.load(Self::KEY);
+        let results = polls.entry(poll.to_string()).or_default();
+        *results.entry(option.to_string()).or_default() += 1;
+        let results = results.clone();
+        self.storage.save(Self::KEY, &polls);
+        Ok(results)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Ballots<S> {
+    storage: S,
+}
+
+impl<S: Storage> Ballots<S> {
+    const KEY: &'static str = "ballots";
+
+    pub const fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    pub fn get(&self, poll: &str) -> Option<String> {
+        let mut ballots: HashMap<String, String> = self.storage.load(Self::KEY);
+        ballots.remove(poll)
+    }
+
+    pub fn record(&self, poll: &str, option: &str) -> bool {
+        let mut ballots: HashMap<String, String> = self.storage.load(Self::KEY);
+        if ballots.contains_key(poll) {
+            return false;
+        }
+        ballots.insert(poll.to_string(), option.to_string());
+        self.storage.save(Self::KEY, &ballots);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        future::Future,
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    use super::{Ballots, LocalPolls, PollBackend, PollError, Results};
+    use crate::storage::{MemoryStorage, Storage};
+
+    fn ready<T>(future: impl Future<Output = T>) -> T {
+        match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(value) => value,
+            Poll::Pending => panic!("local polls never wait"),
+        }
+    }
+
+    fn tally(votes: &[(&str, usize)]) -> Results {
+        votes
+            .iter()
+            .map(|(option, count)| ((*option).to_string(), *count))
+            .collect()
+    }
+
+    #[test]
+    fn one_ballot_per_poll() {
+        let ballots = Ballots::new(MemoryStorage::default());
+        assert_eq!(ballots.get("weather"), None);
+        assert!(ballots.record("weather", "Sunny"));
+        assert!(!ballots.record("weather", "Rainy"));
+        assert_eq!(ballots.get("weather").as_deref(), Some("Sunny"));
+    }
+
+    #[test]
+    fn polls_are_independent() {
+        let storage = MemoryStorage::default();
+        assert!(Ballots::new(storage.clone()).record("weather", "Sunny"));
+        assert!(Ballots::new(storage.clone()).record("footy", "Swans"));
+        assert_eq!(Ballots::new(storage).get("footy").as_deref(), Some("Swans"));
+    }
+
+    #[test]
+    fn voting_records_the_ballot() {
+        let storage = MemoryStorage::default();
+        let polls = LocalPolls::new(storage.clone());
+        assert_eq!(ready(polls.results("weather")), Ok(Results::new()));
+        assert_eq!(
+            ready(polls.vote("weather", "Sunny")),
+            Ok(tally(&[("Sunny", 1)]))
+        );
+        assert_eq!(ready(polls.results("weather")), Ok(tally(&[("Sunny", 1)])));
+        assert_eq!(
+            Ballots::new(storage).get("weather").as_deref(),
+            Some("Sunny")
+        );
+    }
+
+    #[test]
+    fn rejects_a_repeat_vote() {
+        let polls = LocalPolls::new(MemoryStorage::default());
+        ready(polls.vote("weather", "Sunny")).unwrap();
+        assert_eq!(
+            ready(polls.vote("weather", "Rainy")),
+            Err(PollError::AlreadyVoted)
+        );
+        assert_eq!(ready(polls.results("weather")), Ok(tally(&[("Sunny", 1)])));
+    }
+
+    #[test]
+    fn adds_to_the_existing_tally() {
+        let storage = MemoryStorage::default();
+        storage.save(
+            LocalPolls::<MemoryStorage>::KEY,
+            &HashMap::from([("weather", tally(&[("Sunny", 2), ("Rainy", 1)]))]),
+        );
+        let polls = LocalPolls::new(storage);
+        assert_eq!(
+            ready(polls.vote("weather", "Rainy")),
+            Ok(tally(&[("Sunny", 2), ("Rainy", 2)]))
+        );
+        assert_eq!(ready(polls.results("footy")), Ok(Results::new()));
+    }
+}
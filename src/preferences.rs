@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, VariantArray};
+
+use crate::storage::Storage;
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Display, VariantArray, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    Light,
+    Dark,
+    #[default]
+    System,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Display, VariantArray, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum TextSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
+}
+
+impl TextSize {
+    pub const fn class(self) -> &'static str {
+        match self {
+            Self::Small => "text-base/[1.5rem] sm:text-lg/[1.75rem]",
+            Self::Medium => "text-lg/[1.75rem] sm:text-xl/[2rem]",
+            Self::Large => "text-xl/[2rem] sm:text-2xl/[2.25rem]",
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Display, VariantArray, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Font {
+    #[default]
+    Serif,
+    Sans,
+}
+
+impl Font {
+    pub const fn class(self) -> &'static str {
+        match self {
+            Self::Serif => "font-serif",
+            Self::Sans => "font-sans",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preferences {
+    pub theme: Theme,
+    pub text_size: TextSize,
+    pub font: Font,
+}
+
+impl Preferences {
+    const KEY: &'static str = "preferences";
+
+    pub fn load(storage: &impl Storage) -> Self {
+        storage.load(Self::KEY)
+    }
+
+    pub fn save(&self, storage: &impl Storage) {
+        storage.save(Self::KEY, self);
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::ad::Ad;
+use crate::storage::Storage;
+
+pub const FREQUENCY_CAP: u32 = 3;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    pub views: HashMap<String, u32>,
+    pub last: Option<String>,
+}
+
+impl Session {
+    pub fn views(&self, ad: &Ad) -> u32 {
+        self.views.get(&ad.src()).copied().unwrap_or(0)
+    }
+
+    pub fn record(&mut self, ad: &Ad) {
+        *self.views.entry(ad.src()).or_default() += 1;
+        self.last = Some(ad.advertiser.to_string());
+    }
+}
+
+pub fn pick<'a>(ads: &[&'a Ad], session: &Session, rng: &mut impl Rng) -> Option<&'a Ad> {
+    let uncapped = ads
+        .iter()
+        .copied()
+        .filter(|ad| session.views(ad) < FREQUENCY_CAP)
+        .collect::<Vec<_>>();
+    let fresh = uncapped
+        .iter()
+        .copied()
+        .filter(|ad| session.last.as_deref() != Some(ad.advertiser))
+        .collect::<Vec<_>>();
+    let candidates = if fresh.is_empty() { uncapped } else { fresh };
+    candidates
+        .choose_weighted(rng, |ad| ad.weight)
+        .ok()
+        .copied()
+}
+
+#[derive(Debug, Clone)]
+pub struct Impressions<S> {
+    storage: S,
+}
+
+impl<S: Storage> Impressions<S> {
+    const KEY: &'static str = "ad-session";
+
+    pub const fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    pub fn session(&self) -> Session {
+        self.storage.load(Self::KEY)
+    }
+
+    pub fn record(&self, ad: &Ad) {
+        let mut session = self.session();
+        session.record(ad);
+        self.storage.save(Self::KEY, &session);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{pick, Impressions, Session, FREQUENCY_CAP};
+    use crate::{
+        ad::{ad, Ad},
+        storage::MemoryStorage,
+    };
+
+    fn weighted(image: &'static str, advertiser: &'static str, weight: u32) -> Ad {
+        Ad {
+            image,
+            alt: image,
+            weight,
+            ..ad(advertiser, "Services")
+        }
+    }
+
+    fn viewed(ad: &Ad, times: u32) -> Session {
+        let impressions = Impressions::new(MemoryStorage::default());
+        for _ in 0..times {
+            impressions.record(ad);
+        }
+        impressions.session()
+    }
+
+    #[test]
+    fn skips_capped_ads() {
+        let (capped, other) = (weighted("a.png", "Rob's", 1), weighted("b.png", "Rob's", 1));
+        let session = viewed(&capped, FREQUENCY_CAP);
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..20 {
+            assert_eq!(pick(&[&capped, &other], &session, &mut rng), Some(&other));
+        }
+    }
+
+    #[test]
+    fn avoids_the_last_advertiser() {
+        let (last, other) = (
+            weighted("a.png", "Rob's", 100),
+            weighted("b.png", "Wash It", 1),
+        );
+        let session = viewed(&last, 1);
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..20 {
+            assert_eq!(pick(&[&last, &other], &session, &mut rng), Some(&other));
+        }
+    }
+
+    #[test]
+    fn falls_back_to_the_last_advertiser() {
+        let (last, same) = (weighted("a.png", "Rob's", 1), weighted("b.png", "Rob's", 1));
+        let session = viewed(&last, 1);
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(pick(&[&last, &same], &session, &mut rng).is_some());
+    }
+
+    #[test]
+    fn respects_weights() {
+        let (heavy, light, never) = (
+            weighted("a.png", "Rob's", 9),
+            weighted("b.png", "Wash It", 1),
+            weighted("c.png", "Peace", 0),
+        );
+        let mut rng = StdRng::seed_from_u64(0);
+        let heavy_picks = (0..1000)
+            .filter(|_| {
+                let picked = pick(&[&heavy, &light, &never], &Session::default(), &mut rng);
+                assert_ne!(picked, Some(&never));
+                picked == Some(&heavy)
+            })
+            .count();
+        assert!((850..=950).contains(&heavy_picks), "{heavy_picks}");
+    }
+
+    #[test]
+    fn nothing_to_pick() {
+        let capped = weighted("a.png", "Rob's", 1);
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(pick(&[], &Session::default(), &mut rng), None);
+        assert_eq!(
+            pick(&[&capped], &viewed(&capped, FREQUENCY_CAP), &mut rng),
+            None
+        );
+    }
+}
@@ -0,0 +1,328 @@
+use chrono::{Datelike, NaiveDate};
+use itertools::Itertools;
+use strum::Display;
+
+use crate::article::{archive, Article, ARCHIVE_PAGE_SIZE};
+use crate::event::Event;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Route {
+    pub path: &'static str,
+    pub priority: f32,
+    pub change_frequency: ChangeFrequency,
+    pub expand: Expand,
+    pub listed: bool,
+    pub screen: Screen,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum ChangeFrequency {
+    Always,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expand {
+    Latest,
+    Static,
+    Articles,
+    Crosswords,
+    ArchiveMonths,
+    ArchivePages,
+    Events,
+    EventMonths,
+    LandingPages,
+    Dynamic,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Screen {
+    Home,
+    Article,
+    Crossword,
+    Archive,
+    Classifieds,
+    Events,
+    Event,
+    EventMonth,
+    Horoscopes,
+    Saved,
+    AdLanding,
+    AdChoices,
+    Subscribe,
+    NotFound,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page {
+    pub path: String,
+    pub last_modified: Option<NaiveDate>,
+}
+
+pub const HOME: Route = Route {
+    path: "/",
+    priority: 1.0,
+    change_frequency: ChangeFrequency::Daily,
+    expand: Expand::Latest,
+    listed: true,
+    screen: Screen::Home,
+};
+
+pub const ARTICLE: Route = Route {
+    path: "/articles/:id",
+    priority: 0.8,
+    change_frequency: ChangeFrequency::Monthly,
+    expand: Expand::Articles,
+    listed: true,
+    screen: Screen::Article,
+};
+
+pub const CROSSWORD: Route = Route {
+    path: "/crosswords/:id",
+    priority: 0.5,
+    change_frequency: ChangeFrequency::Yearly,
+    expand: Expand::Crosswords,
+    listed: true,
+    screen: Screen::Crossword,
+};
+
+pub const ARCHIVE: Route = Route {
+    path: "/archive/:year/:month",
+    priority: 0.3,
+    change_frequency: ChangeFrequency::Monthly,
+    expand: Expand::ArchiveMonths,
+    listed: true,
+    screen: Screen::Archive,
+};
+
+pub const ARCHIVE_PAGE: Route = Route {
+    path: "/archive/:year/:month/:page",
+    priority: 0.2,
+    change_frequency: ChangeFrequency::Monthly,
+    expand: Expand::ArchivePages,
+    listed: true,
+    screen: Screen::Archive,
+};
+
+pub const EVENTS: Route = Route {
+    path: "/events",
+    priority: 0.6,
+    change_frequency: ChangeFrequency::Weekly,
+    expand: Expand::Static,
+    listed: true,
+    screen: Screen::Events,
+};
+
+pub const EVENT: Route = Route {
+    path: "/events/:id",
+    priority: 0.4,
+    change_frequency: ChangeFrequency::Monthly,
+    expand: Expand::Events,
+    listed: true,
+    screen: Screen::Event,
+};
+
+pub const EVENT_MONTH: Route = Route {
+    path: "/events/:year/:month",
+    priority: 0.3,
+    change_frequency: ChangeFrequency::Weekly,
+    expand: Expand::EventMonths,
+    listed: true,
+    screen: Screen::EventMonth,
+};
+
+pub const HOROSCOPES: Route = Route {
+    path: "/horoscopes",
+    priority: 0.3,
+    change_frequency: ChangeFrequency::Daily,
+    expand: Expand::Static,
+    listed: true,
+    screen: Screen::Horoscopes,
+};
+
+pub const HOROSCOPES_DATE: Route = Route {
+    path: "/horoscopes/:date",
+    priority: 0.0,
+    change_frequency: ChangeFrequency::Never,
+    expand: Expand::Dynamic,
+    listed: false,
+    screen: Screen::Horoscopes,
+};
+
+pub const SAVED: Route = Route {
+    path: "/saved",
+    priority: 0.0,
+    change_frequency: ChangeFrequency::Never,
+    expand: Expand::Static,
+    listed: false,
+    screen: Screen::Saved,
+};
+
+pub const AD_LANDING: Route = Route {
+    path: "/ads/:slug",
+    priority: 0.1,
+    change_frequency: ChangeFrequency::Yearly,
+    expand: Expand::LandingPages,
+    listed: true,
+    screen: Screen::AdLanding,
+};
+
+pub const AD_CHOICES: Route = Route {
+    path: "/ad-choices",
+    priority: 0.0,
+    change_frequency: ChangeFrequency::Never,
+    expand: Expand::Static,
+    listed: false,
+    screen: Screen::AdChoices,
+};
+
+pub const CLASSIFIEDS: Route = Route {
+    path: "/classifieds",
+    priority: 0.5,
+    change_frequency: ChangeFrequency::Weekly,
+    expand: Expand::Static,
+    listed: true,
+    screen: Screen::Classifieds,
+};
+
+pub const SUBSCRIBE: Route = Route {
+    path: "/subscribe",
+    priority: 0.4,
+    change_frequency: ChangeFrequency::Yearly,
+    expand: Expand::Static,
+    listed: true,
+    screen: Screen::Subscribe,
+};
+
+pub const NOT_FOUND: Route = Route {
+    path: "/*",
+    priority: 0.0,
+    change_frequency: ChangeFrequency::Never,
+    expand: Expand::Dynamic,
+    listed: false,
+    screen: Screen::NotFound,
+};
+
+pub const ROUTES: &[Route] = &[
+    HOME,
+    ARTICLE,
+    CROSSWORD,
+    ARCHIVE,
+    ARCHIVE_PAGE,
+    CLASSIFIEDS,
+    EVENTS,
+    EVENT,
+    EVENT_MONTH,
+    HOROSCOPES,
+    HOROSCOPES_DATE,
+    SAVED,
+    AD_LANDING,
+    AD_CHOICES,
+    SUBSCRIBE,
+    NOT_FOUND,
+];
+
+impl Route {
+    pub fn expand(
+        &self,
+        articles: &[Article],
+        crosswords: usize,
+        events: &[Event],
+        landing_pages: &[Article],
+    ) -> Vec<Page> {
+        match self.expand {
+            Expand::Latest => vec![Page {
+                path: self.path.to_string(),
+                last_modified: articles.iter().map(|article| article.date).max(),
+            }],
+            Expand::Static => vec![Page {
+                path: self.path.to_string(),
+                last_modified: None,
+            }],
+            Expand::Articles => articles
+                .iter()
+                .map(|article| Page {
+                    path: self.path.replace(":id", article.id),
+                    last_modified: Some(article.date),
+                })
+                .collect_vec(),
+            Expand::Crosswords => (0..crosswords)
+                .map(|id| Page {
+                    path: self.path.replace(":id", &id.to_string()),
+                    last_modified: None,
+                })
+                .collect_vec(),
+            Expand::ArchiveMonths | Expand::ArchivePages => archive(articles)
+                .into_iter()
+                .flat_map(|((year, month), articles)| {
+                    let last_modified = articles.iter().map(|article| article.date).max();
+                    let pages = if self.expand == Expand::ArchiveMonths {
+                        1..=1
+                    } else {
+                        2..=articles.len().div_ceil(ARCHIVE_PAGE_SIZE)
+                    };
+                    pages.map(move |page| Page {
+                        path: self
+                            .path
+                            .replace(":year", &year.to_string())
+                            .replace(":month", &format!("{month:02}"))
+                            .replace(":page", &page.to_string()),
+                        last_modified,
+                    })
+                })
+                .collect_vec(),
+            Expand::Events => events
+                .iter()
+                .map(|event| Page {
+                    path: self.path.replace(":id", event.id),
+                    last_modified: None,
+                })
+                .collect_vec(),
+            Expand::EventMonths => event_months(events)
+                .into_iter()
+                .map(|(year, month)| Page {
+                    path: self
+                        .path
+                        .replace(":year", &year.to_string())
+                        .replace(":month", &format!("{month:02}")),
+                    last_modified: None,
+                })
+                .collect_vec(),
+            Expand::LandingPages => landing_pages
+                .iter()
+                .map(|page| Page {
+                    path: self.path.replace(":slug", page.id),
+                    last_modified: Some(page.date),
+                })
+                .collect_vec(),
+            Expand::Dynamic => Vec::new(),
+        }
+    }
+}
+
+pub fn pages(
+    articles: &[Article],
+    crosswords: usize,
+    events: &[Event],
+    landing_pages: &[Article],
+) -> Vec<Page> {
+    ROUTES
+        .iter()
+        .flat_map(|route| route.expand(articles, crosswords, events, landing_pages))
+        .collect()
+}
+
+pub fn event_months(events: &[Event]) -> Vec<(i32, u32)> {
+    events
+        .iter()
+        .map(|event| (event.start.year(), event.start.month()))
+        .unique()
+        .sorted_unstable()
+        .collect()
+}
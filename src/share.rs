@@ -0,0 +1,53 @@
+use itertools::Itertools;
+use strum::{Display, VariantArray};
+
+pub const QUOTE_EDGE_WORDS: usize = 4;
+
+pub fn encode(s: &str) -> String {
+    s.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'.' | b'_' | b'~' => {
+                char::from(byte).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+pub fn quote_url(url: &str, quote: &str) -> String {
+    let words = quote.split_whitespace().collect_vec();
+    let text = if words.len() > QUOTE_EDGE_WORDS * 2 {
+        format!(
+            "{},{}",
+            encode(&words[..QUOTE_EDGE_WORDS].join(" ")),
+            encode(&words[words.len() - QUOTE_EDGE_WORDS..].join(" ")),
+        )
+    } else {
+        encode(&words.join(" "))
+    };
+    format!("{url}#:~:text={text}")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, VariantArray)]
+pub enum Intent {
+    Email,
+    X,
+    Facebook,
+}
+
+impl Intent {
+    pub fn href(self, url: &str, text: &str) -> String {
+        let (url, text) = (encode(url), encode(text));
+        match self {
+            Self::Email => format!("mailto:?subject={text}&body={url}"),
+            Self::X => format!("https://twitter.com/intent/tweet?text={text}&url={url}"),
+            Self::Facebook => format!("https://www.facebook.com/sharer/sharer.php?u={url}"),
+        }
+    }
+}
+
+pub fn intents<'a>(url: &'a str, text: &'a str) -> impl Iterator<Item = (Intent, String)> + 'a {
+    Intent::VARIANTS
+        .iter()
+        .map(move |&intent| (intent, intent.href(url, text)))
+}
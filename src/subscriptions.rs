@@ -0,0 +1,261 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    future::Future,
+};
+
+use chrono::{DateTime, Utc};
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+use strum::{Display as StrumDisplay, VariantArray};
+
+use crate::storage::Storage;
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, StrumDisplay, VariantArray, Serialize, Deserialize,
+)]
+pub enum Tier {
+    #[default]
+    Free,
+    Digital,
+    Premium,
+}
+
+impl Tier {
+    pub const fn price(self) -> &'static str {
+        match self {
+            Self::Free => "$0 a month",
+            Self::Digital => "$4 a month",
+            Self::Premium => "$9 a month",
+        }
+    }
+
+    pub const fn perks(self) -> &'static [&'static str] {
+        match self {
+            Self::Free => &["The weekly newsletter", "Five articles a month"],
+            Self::Digital => &["Unlimited articles", "Every crossword", "Fewer ads"],
+            Self::Premium => &[
+                "Everything in Digital",
+                "No ads at all",
+                "A printed edition, delivered by seagull",
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, StrumDisplay, Serialize, Deserialize)]
+pub enum Field {
+    Name,
+    Email,
+    Terms,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SignUp {
+    pub name: String,
+    pub email: String,
+    pub tier: Tier,
+    pub accepted_terms: bool,
+}
+
+impl SignUp {
+    pub fn validate(&self) -> Vec<(Field, &'static str)> {
+        let email = self.email.trim();
+        let valid_email = email.split_once('@').is_some_and(|(user, domain)| {
+            !user.is_empty()
+                && domain.contains('.')
+                && !domain.contains('@')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !email.contains(char::is_whitespace)
+        });
+        [
+            (
+                self.name.trim().is_empty(),
+                Field::Name,
+                "Please enter your name.",
+            ),
+            (
+                !valid_email,
+                Field::Email,
+                "Please enter a valid email address.",
+            ),
+            (
+                !self.accepted_terms,
+                Field::Terms,
+                "Please accept the terms.",
+            ),
+        ]
+        .into_iter()
+        .filter(|(invalid, _, _)| *invalid)
+        .map(|(_, field, message)| (field, message))
+        .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pending {
+    pub token: String,
+    pub sign_up: SignUp,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Subscription {
+    pub name: String,
+    pub email: String,
+    pub tier: Tier,
+    pub since: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionError {
+    Invalid(Vec<(Field, &'static str)>),
+    Expired,
+    Unavailable(String),
+}
+
+impl Display for SubscriptionError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Invalid(errors) => write!(
+                f,
+                "{}",
+                errors
+                    .iter()
+                    .map(|(_, message)| *message)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+            Self::Expired => write!(f, "This confirmation has expired. Please sign up again."),
+            Self::Unavailable(reason) => {
+                write!(f, "Subscriptions are unavailable right now ({reason}).")
+            }
+        }
+    }
+}
+
+pub trait SubscriptionBackend {
+    fn subscribe(
+        &self,
+        sign_up: SignUp,
+    ) -> impl Future<Output = Result<Pending, SubscriptionError>>;
+    fn confirm(&self, token: &str)
+        -> impl Future<Output = Result<Subscription, SubscriptionError>>;
+    fn current(&self) -> impl Future<Output = Option<Subscription>>;
+    fn cancel(&self) -> impl Future<Output = Result<(), SubscriptionError>>;
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalSubscriptions<S> {
+    storage: S,
+}
+
+impl<S: Storage> LocalSubscriptions<S> {
+    const PENDING: &'static str = "pending-subscription";
+    const SUBSCRIPTION: &'static str = "subscription";
+
+    pub const fn new(storage: S) -> Self {
+        Self { storage }
+    }
+}
+
+impl<S: Storage> SubscriptionBackend for LocalSubscriptions<S> {
+    async fn subscribe(&self, sign_up: SignUp) -> Result<Pending, SubscriptionError> {
+        let errors = sign_up.validate();
+        if !errors.is_empty() {
+            return Err(SubscriptionError::Invalid(errors));
+        }
+        let pending = Pending {
+            token: thread_rng()
+                .sample_iter(Alphanumeric)
+                .take(16)
+                .map(char::from)
+                .collect(),
+            sign_up: SignUp {
+                name: sign_up.name.trim().to_string(),
+                email: sign_up.email.trim().to_lowercase(),
+                ..sign_up
+            },
+        };
+        self.storage.save(Self::PENDING, &Some(&pending));
+        Ok(pending)
+    }
+
+    async fn confirm(&self, token: &str) -> Result<Subscription, SubscriptionError> {
+        let pending: Option<Pending> = self.storage.load(Self::PENDING);
+        let pending = pending
+            .filter(|pending| pending.token == token)
+            .ok_or(SubscriptionError::Expired)?;
+        let subscription = Subscription {
+            name: pending.sign_up.name,
+            email: pending.sign_up.email,
+            tier: pending.sign_up.tier,
+            since: Utc::now(),
+        };
+        self.storage.remove(Self::PENDING);
+        self.storage.save(Self::SUBSCRIPTION, &Some(&subscription));
+        Ok(subscription)
+    }
+
+    async fn current(&self) -> Option<Subscription> {
+        self.storage.load(Self::SUBSCRIPTION)
+    }
+
+    async fn cancel(&self) -> Result<(), SubscriptionError> {
+        self.storage.remove(Self::SUBSCRIPTION);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign_up(name: &str, email: &str, accepted_terms: bool) -> SignUp {
+        SignUp {
+            name: name.to_string(),
+            email: email.to_string(),
+            tier: Tier::Digital,
+            accepted_terms,
+        }
+    }
+
+    fn fields(sign_up: &SignUp) -> Vec<Field> {
+        sign_up
+            .validate()
+            .into_iter()
+            .map(|(field, _)| field)
+            .collect()
+    }
+
+    #[test]
+    fn accepts_a_complete_sign_up() {
+        assert!(fields(&sign_up("Ada", " ada@example.com ", true)).is_empty());
+    }
+
+    #[test]
+    fn reports_every_invalid_field() {
+        assert_eq!(
+            fields(&sign_up("  ", "", false)),
+            [Field::Name, Field::Email, Field::Terms]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_emails() {
+        for email in [
+            "ada",
+            "@example.com",
+            "ada@example",
+            "ada@.com",
+            "ada@example.",
+            "a da@example.com",
+            "a@b@example.com",
+        ] {
+            assert_eq!(
+                fields(&sign_up("Ada", email, true)),
+                [Field::Email],
+                "{email}"
+            );
+        }
+    }
+}
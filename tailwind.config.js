@@ -4,6 +4,7 @@ module.exports = {
     "index.html",
     "src/**/*.rs",
   ],
+  darkMode: 'class',
   theme: {
     extend: {
       fontFamily: {